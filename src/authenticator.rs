@@ -27,5 +27,5 @@ pub fn get_authorization(path: &str, date: &str) -> String {
     let data = format!("{}{}", path, date);
     signer.update(data.as_bytes()).expect("Failed to update HMAC");
 
-    base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &signer.sign_to_vec().expect("HMAC sign failed"))
+    base64::Engine::encode(&base64::engine::general_purpose::STANDARD, signer.sign_to_vec().expect("HMAC sign failed"))
 }
@@ -0,0 +1,173 @@
+use clap::ValueEnum;
+use sbb_api::models::location::StationRef;
+use sbb_api::Language;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Settings read from `config.toml`. Every value can be overridden on the command line.
+///
+/// ```toml
+/// language = "en"
+/// format = "table"
+/// timeout = 30
+/// class = "1"
+/// card = "half-fare"
+/// history = false
+///
+/// [aliases]
+/// home = "8503000"
+/// office = { name = "Bern", reference = "8507000" }
+/// gym = "Zürich, Escher-Wyss-Platz"
+/// ```
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Language of texts returned by the API
+    pub language: Option<Language>,
+    /// Default output format (same values as `--format`)
+    pub format: Option<String>,
+    /// Request timeout in seconds
+    pub timeout: Option<u64>,
+    /// Default travel class for prices (same values as `--class`)
+    pub class: Option<String>,
+    /// Default travelcard for prices (same values as `--card`)
+    pub card: Option<String>,
+    /// Whether to remember searches for `sbb history` and `sbb again` (default: true)
+    pub history: Option<bool>,
+    /// Short names for places, usable wherever a station is expected
+    #[serde(default)]
+    pub aliases: HashMap<String, Alias>,
+}
+
+/// A place an alias stands for: a name or UIC reference, or both.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Alias {
+    Station(String),
+    Place { name: String, reference: Option<String> },
+}
+
+impl Alias {
+    pub fn station(&self) -> StationRef {
+        match self {
+            Alias::Station(s) => s.parse().unwrap_or_else(|e| match e {}),
+            Alias::Place { name, reference } => StationRef::new(name, reference.as_deref()),
+        }
+    }
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/sbb/config.toml`, or `~/.config/sbb/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join("sbb").join("config.toml"))
+    }
+
+    /// Load the config from `path`, or from the default location when none is given.
+    /// A missing default config is not an error; an explicitly given one must exist.
+    pub fn load(path: Option<&Path>) -> Result<Config, String> {
+        let (path, required) = match path {
+            Some(p) => (p.to_path_buf(), true),
+            None => match Config::default_path() {
+                Some(p) => (p, false),
+                None => return Ok(Config::default()),
+            },
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !required => return Ok(Config::default()),
+            Err(e) => return Err(format!("cannot read {}: {}", path.display(), e)),
+        };
+        text.parse().map_err(|e| format!("invalid config {}: {}", path.display(), e))
+    }
+
+    /// The station an alias stands for, if `input` is one.
+    pub fn alias(&self, input: &str) -> Option<StationRef> {
+        self.aliases.get(input.trim()).map(Alias::station)
+    }
+
+    /// The station an input stands for: the alias it names, or the input itself.
+    pub fn resolve(&self, input: &str) -> StationRef {
+        self.alias(input).unwrap_or_else(|| input.parse().unwrap_or_else(|e| match e {}))
+    }
+
+    /// Replace a station given by name only with the alias of that name, if any.
+    pub fn resolve_station(&self, station: StationRef) -> StationRef {
+        match station.reference {
+            None => self.alias(&station.name).unwrap_or(station),
+            Some(_) => station,
+        }
+    }
+
+    /// Parse a value of a command line enum (format, class, card), as clap would.
+    pub fn value<T: ValueEnum>(key: &str, value: Option<&str>) -> Result<Option<T>, String> {
+        value
+            .map(|v| T::from_str(v, true).map_err(|_| format!("invalid value '{}' for '{}' in config", v, key)))
+            .transpose()
+    }
+}
+
+impl std::str::FromStr for Config {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutputFormat;
+
+    #[test]
+    fn test_parse_config() {
+        let config: Config = r#"
+            language = "fr"
+            format = "table"
+            timeout = 30
+
+            [aliases]
+            home = "8503000"
+            office = { name = "Bern", reference = "8507000" }
+            gym = "Zürich, Escher-Wyss-Platz"
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(config.language, Some(Language::Fr));
+        assert_eq!(config.timeout, Some(30));
+        assert_eq!(Config::value::<OutputFormat>("format", config.format.as_deref()), Ok(Some(OutputFormat::Table)));
+        assert!(Config::value::<OutputFormat>("format", Some("yaml")).is_err());
+        assert!(Config::default().aliases.is_empty());
+
+        assert!("colour = true".parse::<Config>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_aliases() {
+        let config: Config = r#"
+            [aliases]
+            home = "8503000"
+            office = { name = "Bern", reference = "8507000" }
+            gym = "Zürich, Escher-Wyss-Platz"
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(config.resolve("home"), StationRef::new("", Some("8503000")));
+        assert_eq!(config.resolve(" office "), StationRef::new("Bern", Some("8507000")));
+        assert_eq!(config.resolve("gym"), StationRef::new("Zürich, Escher-Wyss-Platz", None));
+        assert_eq!(config.resolve("Olten"), StationRef::new("Olten", None));
+
+        let station = config.resolve_station("office".parse().unwrap());
+        assert_eq!(station.reference.as_deref(), Some("8507000"));
+        // UIC references are never aliases
+        let station = config.resolve_station(StationRef::new("home", Some("8500010")));
+        assert_eq!(station.reference.as_deref(), Some("8500010"));
+    }
+}
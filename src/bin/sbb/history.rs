@@ -0,0 +1,164 @@
+use chrono::{DateTime, Utc};
+use sbb_api::models::location::StationRef;
+use sbb_api::models::trip::TripSearchResponse;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Number of searches kept; older ones are dropped
+pub const MAX_ENTRIES: usize = 100;
+
+/// One connection search, as typed and as resolved.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// The search as typed, e.g. "home → office"
+    pub query: String,
+    pub from: StationRef,
+    pub to: StationRef,
+    pub arrival: bool,
+    pub searched_at: DateTime<Utc>,
+}
+
+impl HistoryEntry {
+    /// Two entries are the same search if they resolve to the same stations.
+    fn same_search(&self, other: &HistoryEntry) -> bool {
+        self.from == other.from && self.to == other.to && self.arrival == other.arrival
+    }
+}
+
+/// Past searches, most recent first.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// `$XDG_STATE_HOME/sbb/history.json`, or `~/.local/state/sbb/history.json`
+    pub fn default_path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_STATE_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state")))?;
+        Some(base.join("sbb").join("history.json"))
+    }
+
+    /// Load the history; a missing file is an empty history.
+    pub fn load(path: &Path) -> Result<History, String> {
+        match std::fs::read(path) {
+            Ok(data) => serde_json::from_slice(&data).map_err(|e| format!("invalid history {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(format!("cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        }
+        let data = serde_json::to_vec_pretty(self).expect("Failed to serialize JSON");
+        std::fs::write(path, data).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+
+    /// Add a search at the top, replacing an earlier identical one
+    pub fn add(&mut self, entry: HistoryEntry) {
+        self.entries.retain(|e| !e.same_search(&entry));
+        self.entries.insert(0, entry);
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    /// The N-th most recent search (1 = last)
+    pub fn get(&self, n: usize) -> Option<&HistoryEntry> {
+        n.checked_sub(1).and_then(|i| self.entries.get(i))
+    }
+}
+
+/// Where and under which name a search is remembered once it succeeded.
+pub struct Recorder {
+    pub path: PathBuf,
+    pub query: String,
+}
+
+impl Recorder {
+    /// Remember a search. Stations given by name only get the UIC reference the API resolved
+    /// them to, so repeating the search skips the name lookup.
+    pub fn record(&self, from: StationRef, to: StationRef, arrival: bool, resp: &TripSearchResponse) -> Result<(), String> {
+        let anchors = resp.trips.first().map(|t| &t.summary);
+        let resolve = |station: StationRef, resolved: Option<&String>| StationRef {
+            reference: station.reference.or_else(|| resolved.cloned()),
+            ..station
+        };
+        let mut history = History::load(&self.path)?;
+        history.add(HistoryEntry {
+            query: self.query.clone(),
+            from: resolve(from, anchors.and_then(|s| s.departure_anchor.place_reference.as_ref())),
+            to: resolve(to, anchors.and_then(|s| s.arrival_anchor.place_reference.as_ref())),
+            arrival,
+            searched_at: Utc::now(),
+        });
+        history.save(&self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(query: &str, from: &str, to: &str) -> HistoryEntry {
+        HistoryEntry {
+            query: query.to_string(),
+            from: from.parse().unwrap(),
+            to: to.parse().unwrap(),
+            arrival: false,
+            searched_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_add_deduplicates_and_caps() {
+        let mut history = History::default();
+        history.add(entry("home → office", "8503000", "8507000"));
+        history.add(entry("Basel → Bern", "Basel", "Bern"));
+        // The same stations typed differently are the same search
+        history.add(entry("Zürich HB → office", "8503000", "8507000"));
+
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.get(1).unwrap().query, "Zürich HB → office");
+        assert_eq!(history.get(2).unwrap().query, "Basel → Bern");
+        assert!(history.get(0).is_none());
+        assert!(history.get(3).is_none());
+
+        for i in 0..MAX_ENTRIES + 10 {
+            history.add(entry("", &format!("Stop {}", i), "Bern"));
+        }
+        assert_eq!(history.entries.len(), MAX_ENTRIES);
+        assert_eq!(history.get(1).unwrap().from.name, format!("Stop {}", MAX_ENTRIES + 9));
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("sbb-history-test-{}", std::process::id()))
+            .join("history.json");
+        assert!(History::load(&path).unwrap().entries.is_empty());
+
+        let recorder = Recorder { path: path.clone(), query: "home → Bern".to_string() };
+        let none = TripSearchResponse::default();
+        recorder.record("8503000".parse().unwrap(), "Bern".parse().unwrap(), true, &none).unwrap();
+        recorder.record("8503000".parse().unwrap(), "Bern".parse().unwrap(), true, &none).unwrap();
+
+        let history = History::load(&path).unwrap();
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].from.reference.as_deref(), Some("8503000"));
+        assert!(history.entries[0].arrival);
+
+        // A search by name is remembered with the stations the API resolved
+        let resp = crate::fixtures::response("sbb_api_response_0.json");
+        let recorder = Recorder { path: path.clone(), query: "Basel → Bern".to_string() };
+        recorder.record("Basel".parse().unwrap(), "Bern".parse().unwrap(), false, &resp).unwrap();
+
+        let entry = History::load(&path).unwrap().get(1).cloned().unwrap();
+        assert_eq!(entry.from, StationRef::new("Basel", Some("8500010")));
+        assert_eq!(entry.to, StationRef::new("Bern", Some("8507000")));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,2200 @@
+use clap::{ArgAction, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use futures::StreamExt;
+use colored::control;
+use colored::Colorize;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use sbb_api::board::get_station_board;
+use sbb_api::connections::{get_connections, load_page};
+use sbb_api::datetime::{self, format_clock_time};
+use sbb_api::disruptions::{get_disruptions, DisruptionFilter};
+use sbb_api::export::flat::{self, leg_rows, trip_rows, FlatRecord};
+use sbb_api::export::geojson::trips_to_geojson;
+use sbb_api::export::ics::{trips_to_ics, IcsEvents};
+use sbb_api::connections::ConnectionCache;
+use sbb_api::filter::TripFilter;
+use sbb_api::formation::{get_formation, get_leg_formation};
+use sbb_api::matrix::travel_time_matrix;
+use sbb_api::meeting::find_meeting_point;
+use sbb_api::overview::multi_day_overview;
+use sbb_api::models::location::StationRef;
+use sbb_api::ranking::{retain_pareto_front, sort_trips, Criterion};
+use sbb_api::reachability::ReachabilityOptions;
+use sbb_api::service_day::{first_connection, last_connection, service_day_of};
+use sbb_api::template::Template;
+use sbb_api::watch::{watch_trip, WatchEvent, WatchOptions};
+use sbb_api::Language;
+use sbb_api::models::formation::{CoachFeature, Formation};
+use sbb_api::models::offer::{Price, Reduction, TravelClass, TravellerOptions};
+use sbb_api::models::place::{Coordinates, NearbyPlace, Place};
+use sbb_api::offers::get_offers;
+use sbb_api::places::{get_places, nearby_places};
+use sbb_api::planner::{plan_arrive_by, ArriveByOptions};
+use sbb_api::models::location::SearchDateTimeType;
+use sbb_api::models::trip::{Disruption, PageDirection, RtType, Trip, TripLeg, TripSearchResponse};
+use simple_error::SimpleError;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::time::timeout;
+
+mod config;
+mod history;
+mod tui;
+
+/// Recorded responses for the binary's tests, which cannot reach the library's test-only fixtures.
+#[cfg(test)]
+mod fixtures {
+    use sbb_api::models::trip::TripSearchResponse;
+
+    /// The recorded response in `file`, e.g. "sbb_api_response_0.json" (Basel SBB → Bern).
+    pub(crate) fn response(file: &str) -> TripSearchResponse {
+        let data = std::fs::read(format!("./resources/test/{}", file)).unwrap();
+        serde_json::from_slice(&data).unwrap()
+    }
+}
+
+use config::Config;
+use history::{History, Recorder};
+
+// Exit codes: 0=success, 1=error, 2=no results
+const EXIT_SUCCESS: i32 = 0;
+const EXIT_ERROR: i32 = 1;
+const EXIT_NO_RESULTS: i32 = 2;
+
+// Search radius when resolving coordinates to the nearest stop
+const NEARBY_RADIUS_M: f64 = 1500.0;
+
+// Most result pages loaded to reach --count
+const MAX_PAGES: usize = 10;
+
+// Most days searched by --days and --until
+const MAX_DAYS: usize = 60;
+
+// Timeout duration for requests, unless set with --timeout or in the config
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+static REQUEST_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
+fn request_timeout() -> Duration {
+    *REQUEST_TIMEOUT.get_or_init(|| Duration::from_secs(REQUEST_TIMEOUT_SECS))
+}
+
+/// SBB API error response structure (for parsing 400 Bad Request errors)
+#[derive(Deserialize, Debug)]
+struct SbbApiError {
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Error types for better handling
+enum AppError {
+    Timeout,
+    HttpError(StatusCode, String),
+    NetworkError(String),
+    ParseError(String),
+}
+
+impl AppError {}
+
+/// Print error message in red with optional retry suggestion
+fn print_error(msg: &str, show_retry: bool) {
+    eprintln!("{} {}", "error:".red().bold(), msg.red());
+    if show_retry {
+        eprintln!("{} {}", "hint:".yellow().bold(), "Please check your network connection and try again.".yellow());
+    }
+}
+
+/// Print error message in red (no retry suggestion)
+fn print_error_simple(msg: &str) {
+    print_error(msg, false);
+}
+
+/// Try to parse API error message from HTTP response body
+fn parse_api_error(body: &str, status: StatusCode) -> String {
+    // Try to parse as SBB API error JSON
+    if let Ok(api_error) = serde_json::from_str::<SbbApiError>(body) {
+        if let Some(msg) = api_error.message {
+            return msg;
+        }
+        if let Some(msg) = api_error.error {
+            return msg;
+        }
+        if let Some(msg) = api_error.error_description {
+            return msg;
+        }
+    }
+
+    // If JSON parsing fails but body is not empty, return a truncated version of body
+    if !body.is_empty() {
+        let truncated = if body.len() > 200 {
+            format!("{}...", &body[..200])
+        } else {
+            body.to_string()
+        };
+        return format!("Server returned: {}", truncated);
+    }
+
+    // Fallback to status description
+    status.canonical_reason().unwrap_or("Unknown error").to_string()
+}
+
+/// Convert SimpleError to AppError with better context
+fn convert_error(err: SimpleError) -> AppError {
+    let err_str = err.to_string();
+
+    // Check for common network error patterns
+    let lower_err = err_str.to_lowercase();
+    if lower_err.contains("connection")
+        || lower_err.contains("network")
+        || lower_err.contains("dns")
+        || lower_err.contains("socket")
+        || lower_err.contains("tcp")
+    {
+        return AppError::NetworkError(err_str);
+    }
+
+    // Check for HTTP errors (format: "HTTP {status}: {body}")
+    if let Some(pos) = err_str.find("HTTP ") {
+        let http_part = &err_str[pos..];
+        if let Some(space_pos) = http_part.find(' ') {
+            let status_str = &http_part[space_pos + 1..];
+            if let Some(colon_pos) = status_str.find(':') {
+                let status_code_str = &status_str[..colon_pos];
+                let body = status_str[colon_pos + 1..].trim();
+
+                if let Ok(status) = status_code_str.parse::<StatusCode>() {
+                    return AppError::HttpError(status, body.to_string());
+                }
+            }
+        }
+    }
+
+    // Check for timeout in error message
+    if lower_err.contains("timeout") {
+        return AppError::Timeout;
+    }
+
+    // Check for JSON parse errors
+    if lower_err.contains("json") || lower_err.contains("parse") {
+        return AppError::ParseError(err_str);
+    }
+
+    // Default: treat as unknown HTTP error
+    AppError::ParseError(err_str)
+}
+
+/// Handle HTTP errors with detailed messages and helpful hints
+fn handle_http_error(status: StatusCode, body: &str) {
+    let error_msg = if status == StatusCode::BAD_REQUEST {
+        // Special handling for 400 Bad Request - try to show API error message
+        parse_api_error(body, status)
+    } else {
+        // For other HTTP errors, show status and truncated body
+        if body.is_empty() {
+            status.canonical_reason().unwrap_or("HTTP error").to_string()
+        } else {
+            let truncated = if body.len() > 200 {
+                format!("{}...", &body[..200])
+            } else {
+                body.to_string()
+            };
+            format!(
+                "HTTP {} {} - {}",
+                status.as_u16(),
+                status.canonical_reason().unwrap_or(""),
+                truncated
+            )
+        }
+    };
+
+    // Show HTTP errors in red
+    print_error_simple(&error_msg);
+
+    // Add specific hint for certain status codes
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        eprintln!("{} {}", "hint:".yellow().bold(), "The API may have changed. Consider updating the application.".yellow());
+    } else if status == StatusCode::TOO_MANY_REQUESTS {
+        eprintln!("{} {}", "hint:".yellow().bold(), "You are being rate-limited. Please wait a moment and try again.".yellow());
+    } else if status == StatusCode::BAD_REQUEST {
+        eprintln!("{} {}", "hint:".yellow().bold(), "Check your station names or UIC references for typos.".yellow());
+    }
+}
+
+/// Report an error with the matching message and hint, then exit
+fn exit_with_error(app_error: AppError) -> ! {
+    match app_error {
+        AppError::Timeout => {
+            print_error(
+                &format!("request timed out after {} seconds", request_timeout().as_secs()),
+                true,
+            );
+        }
+        AppError::NetworkError(msg) => {
+            // Show connection errors in red with retry suggestion
+            print_error(&format!("connection failed: {}", msg), true);
+        }
+        AppError::HttpError(status, body) => {
+            // Handle HTTP errors with detailed messages
+            handle_http_error(status, &body);
+        }
+        AppError::ParseError(msg) => {
+            // Show parse errors in red
+            print_error(&format!("failed to parse response: {}", msg), false);
+        }
+    }
+    std::process::exit(EXIT_ERROR);
+}
+
+/// Await an API call with the request timeout, exiting with a reported error on failure
+async fn request_or_exit<T>(request: impl Future<Output = Result<T, SimpleError>>) -> T {
+    match timeout(request_timeout(), request).await {
+        Ok(Ok(r)) => r,
+        Ok(Err(e)) => exit_with_error(convert_error(e)),
+        Err(_) => exit_with_error(AppError::Timeout),
+    }
+}
+
+// Enable colors based on terminal support and --no-color flag
+fn init_colors(no_color: bool) {
+    if no_color {
+        control::set_override(false);
+    } else if atty::is(atty::Stream::Stdout) {
+        control::set_override(true);
+    } else {
+        control::set_override(false);
+    }
+}
+
+/// Output format for search results
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+enum OutputFormat {
+    /// Colored, human-readable connection list
+    Text,
+    /// Raw API response
+    Json,
+    /// GeoJSON FeatureCollection of all connections
+    Geojson,
+    /// Comma-separated values
+    Csv,
+    /// One JSON object per line
+    Ndjson,
+    /// Markdown table
+    Markdown,
+    /// Plain aligned table
+    Table,
+    /// iCalendar events, for importing connections into a calendar
+    Ics,
+}
+
+/// Travel class used for prices
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ClassArg {
+    #[value(name = "1")]
+    First,
+    #[value(name = "2")]
+    Second,
+}
+
+/// Criterion for sorting and Pareto filtering
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CriterionArg {
+    /// Shortest travel time
+    Duration,
+    /// Fewest transfers
+    Transfers,
+    /// Earliest arrival
+    Arrival,
+    /// Lowest occupancy
+    Occupancy,
+    /// Least walking
+    Walking,
+}
+
+impl From<CriterionArg> for Criterion {
+    fn from(arg: CriterionArg) -> Self {
+        match arg {
+            CriterionArg::Duration => Criterion::Duration,
+            CriterionArg::Transfers => Criterion::Transfers,
+            CriterionArg::Arrival => Criterion::Arrival,
+            CriterionArg::Occupancy => Criterion::Occupancy,
+            CriterionArg::Walking => Criterion::Walking,
+        }
+    }
+}
+
+/// Minimum change time, either everywhere or at one station
+#[derive(Clone)]
+enum MinChangeArg {
+    Global(i64),
+    At(String, i64),
+}
+
+/// Parse `MIN` or `STATION=MIN`
+fn parse_min_change(s: &str) -> Result<MinChangeArg, String> {
+    let parse_minutes = |m: &str| {
+        m.trim()
+            .parse::<i64>()
+            .map_err(|_| format!("invalid number of minutes '{}'", m.trim()))
+    };
+    match s.rsplit_once('=') {
+        Some((station, minutes)) => Ok(MinChangeArg::At(station.trim().to_string(), parse_minutes(minutes)?)),
+        None => Ok(MinChangeArg::Global(parse_minutes(s)?)),
+    }
+}
+
+/// Travelcard used for prices
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CardArg {
+    None,
+    HalfFare,
+    Ga,
+}
+
+#[derive(Parser)]
+#[command(name = "sbb", about = "Query SBB train connections", version)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// `sbb FROM TO` is short for `sbb trip FROM TO`
+    #[command(flatten)]
+    trip: TripArgs,
+    /// Print debug information to stderr
+    #[arg(short = 'd', long = "debug", global = true)]
+    debug: bool,
+    /// Output raw JSON response (same as --format json)
+    #[arg(short = 'j', long = "json", conflicts_with = "format", global = true)]
+    json: bool,
+    /// Output format [default: text]
+    #[arg(long = "format", value_name = "FORMAT", value_enum, global = true)]
+    format: Option<OutputFormat>,
+    /// Disable colored output
+    #[arg(long = "no-color", global = true)]
+    no_color: bool,
+    /// Request timeout in seconds [default: 15]
+    #[arg(long = "timeout", value_name = "SECONDS", global = true)]
+    timeout: Option<u64>,
+    /// Language of API texts such as disruption messages (de, fr, it, en)
+    #[arg(long = "language", value_name = "LANG", global = true)]
+    language: Option<Language>,
+    /// Config file [default: $XDG_CONFIG_HOME/sbb/config.toml]
+    #[arg(long = "config", value_name = "PATH", global = true)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Args, Default)]
+struct TripArgs {
+    /// Departure station or address
+    #[arg(group = "from_group", value_name = "FROM")]
+    from: Option<String>,
+    /// Arrival station or address
+    #[arg(group = "to_group", value_name = "TO")]
+    to: Option<String>,
+    /// UIC reference for departure (e.g. 8503000 for Zürich HB)
+    #[arg(long = "from-ref", value_name = "UIC", group = "from_group")]
+    from_ref: Option<String>,
+    /// Depart from the stop nearest to these coordinates (e.g. 47.378,8.540).
+    /// The only positional argument is then taken as the destination.
+    #[arg(long = "from-coords", value_name = "LAT,LON", conflicts_with = "from_ref")]
+    from_coords: Option<Coordinates>,
+    /// UIC reference for arrival
+    #[arg(long = "to-ref", value_name = "UIC", group = "to_group")]
+    to_ref: Option<String>,
+    /// Departure time: 7:45, now, in 20 min, tomorrow 7:45, ...
+    #[arg(long = "at", value_name = "TIME", allow_hyphen_values = true)]
+    at: Option<String>,
+    /// Departure date: 15.3., 2026-03-15, tomorrow, friday, +1d, ...
+    #[arg(long = "date", value_name = "DATE", allow_hyphen_values = true)]
+    date: Option<String>,
+    /// Search for connections arriving at the given time instead of departing
+    #[arg(long = "arrival")]
+    arrival: bool,
+    /// Only show direct connections
+    #[arg(long = "direct", conflicts_with = "max_transfers")]
+    direct: bool,
+    /// Only show connections with at most this many transfers
+    #[arg(long = "max-transfers", value_name = "N")]
+    max_transfers: Option<usize>,
+    /// Minimum change time in minutes, everywhere (6) or at one station (Olten=6). Repeatable
+    #[arg(long = "min-change", value_name = "[STATION=]MIN", value_parser = parse_min_change)]
+    min_change: Vec<MinChangeArg>,
+    /// Avoid connections through this station (name or UIC reference). Repeatable
+    #[arg(long = "exclude", value_name = "STATION")]
+    exclude: Vec<String>,
+    /// Sort connections by one or more criteria (comma-separated, most significant first)
+    #[arg(long = "sort", value_name = "CRITERIA", value_enum, value_delimiter = ',')]
+    sort: Vec<CriterionArg>,
+    /// Drop connections that are worse than another in every respect.
+    /// Compares by the --sort criteria, or duration, transfers and arrival by default
+    #[arg(long = "pareto")]
+    pareto: bool,
+    /// Also show the connections before the first result. Repeat for more pages
+    #[arg(long = "earlier", action = ArgAction::Count)]
+    earlier: u8,
+    /// Also show the connections after the last result. Repeat for more pages
+    #[arg(long = "later", action = ArgAction::Count)]
+    later: u8,
+    /// Load later connections until N are found (after filtering) and show only those
+    #[arg(long = "count", value_name = "N")]
+    count: Option<usize>,
+    /// Only show the last connection of the day (after-midnight trains included)
+    #[arg(long = "last", conflicts_with_all = ["first", "at", "arrival", "days", "until", "earlier", "later", "count"])]
+    last: bool,
+    /// Only show the first connection of the day
+    #[arg(long = "first", conflicts_with_all = ["at", "arrival", "days", "until", "earlier", "later", "count"])]
+    first: bool,
+    /// Plan to arrive by this time: show the latest departure that makes it without risky changes
+    #[arg(
+        long = "arrive-by",
+        value_name = "TIME",
+        allow_hyphen_values = true,
+        conflicts_with_all = ["at", "arrival", "first", "last", "days", "until", "earlier", "later", "count"]
+    )]
+    arrive_by: Option<String>,
+    /// Minutes to arrive before --arrive-by
+    #[arg(long = "buffer", value_name = "MIN", requires = "arrive_by", default_value_t = 0)]
+    buffer: u32,
+    /// Search the same time on N consecutive days and show the first suitable connection of each
+    #[arg(long = "days", value_name = "N", conflicts_with_all = ["earlier", "later", "count", "pick", "pareto", "sort", "prices", "formation"])]
+    days: Option<usize>,
+    /// Like --days, for every day up to and including this date
+    #[arg(long = "until", value_name = "DATE", allow_hyphen_values = true, conflicts_with = "days", conflicts_with_all = ["earlier", "later", "count", "pick", "pareto", "sort", "prices", "formation"])]
+    until: Option<String>,
+    /// Maximum number of concurrent requests for --days and --until [default: 4]
+    #[arg(long = "concurrency", value_name = "N")]
+    concurrency: Option<usize>,
+    /// Only keep the N-th connection (1 = first), after filtering and sorting
+    #[arg(long = "pick", value_name = "N")]
+    pick: Option<usize>,
+    /// Print one line per connection using a template, e.g. "{line} {departure}{?delay} ({delay}){/delay} {quay}"
+    #[arg(long = "template", value_name = "TEMPLATE", conflicts_with_all = ["format", "json"])]
+    template: Option<Template>,
+    /// Output one row or calendar event per leg instead of per connection
+    /// (csv, ndjson, markdown, table and ics formats)
+    #[arg(long = "per-leg")]
+    per_leg: bool,
+    /// Show the cheapest price for each connection
+    #[arg(long = "prices")]
+    prices: bool,
+    /// Travel class for prices [default: 2]
+    #[arg(long = "class", value_name = "CLASS", value_enum)]
+    class: Option<ClassArg>,
+    /// Travelcard for prices [default: none]
+    #[arg(long = "card", value_name = "CARD", value_enum)]
+    card: Option<CardArg>,
+    /// Show the train composition of long-distance legs
+    #[arg(long = "formation")]
+    formation: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Search connections between two places (the default command)
+    Trip(Box<TripArgs>),
+    /// Show the departures or arrivals at a station
+    Board(BoardArgs),
+    /// Search places by name, or stops near coordinates
+    Place(PlaceArgs),
+    /// Interactive full-screen connection search
+    Tui(TuiArgs),
+    /// Generate shell completions
+    Completions(CompletionsArgs),
+    /// List current network-wide disruptions
+    Disruptions(DisruptionsArgs),
+    /// Show the composition of a train relative to the platform sectors
+    Formation(FormationArgs),
+    /// Compute a travel-time matrix between stations
+    Matrix(MatrixArgs),
+    /// Find the station where several travellers can best meet
+    Meet(MeetArgs),
+    /// Monitor a connection and report delays, platform changes and cancellations until it arrives
+    Watch(WatchArgs),
+    /// List past searches
+    History(HistoryArgs),
+    /// Run a past search again, at the current time
+    Again(AgainArgs),
+}
+
+#[derive(Args)]
+struct BoardArgs {
+    /// Station (name or UIC reference)
+    #[arg(value_name = "STATION")]
+    station: StationRef,
+    /// Time: 7:45, now, in 20 min, tomorrow 7:45, ...
+    #[arg(long = "at", value_name = "TIME", allow_hyphen_values = true)]
+    at: Option<String>,
+    /// Date: 15.3., 2026-03-15, tomorrow, friday, +1d, ...
+    #[arg(long = "date", value_name = "DATE", allow_hyphen_values = true)]
+    date: Option<String>,
+    /// Show arrivals instead of departures
+    #[arg(long = "arrivals")]
+    arrivals: bool,
+    /// Maximum number of entries
+    #[arg(long = "limit", value_name = "N", default_value_t = 15)]
+    limit: usize,
+}
+
+#[derive(Args)]
+#[command(group = clap::ArgGroup::new("place_query").required(true))]
+struct PlaceArgs {
+    /// Name to search for
+    #[arg(value_name = "QUERY", group = "place_query")]
+    query: Option<String>,
+    /// List stops near these coordinates instead (e.g. 47.378,8.540)
+    #[arg(long = "near", value_name = "LAT,LON", group = "place_query")]
+    near: Option<Coordinates>,
+    /// Search radius in meters for --near
+    #[arg(long = "radius", value_name = "METERS", default_value_t = NEARBY_RADIUS_M)]
+    radius: f64,
+}
+
+#[derive(Args)]
+struct TuiArgs {
+    /// Departure station to start with
+    #[arg(value_name = "FROM")]
+    from: Option<String>,
+    /// Arrival station to start with
+    #[arg(value_name = "TO")]
+    to: Option<String>,
+    /// Refresh interval in seconds (default: as suggested by the API)
+    #[arg(long = "refresh", value_name = "SECONDS")]
+    refresh: Option<u64>,
+}
+
+#[derive(Args)]
+struct HistoryArgs {
+    /// Forget all past searches
+    #[arg(long = "clear")]
+    clear: bool,
+}
+
+#[derive(Args)]
+struct AgainArgs {
+    /// Which search to repeat, as numbered by `sbb history` (1 = most recent)
+    #[arg(value_name = "N", default_value_t = 1)]
+    n: usize,
+}
+
+#[derive(Args)]
+struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_name = "SHELL", value_enum)]
+    shell: Shell,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    /// Departure station (name or UIC reference)
+    #[arg(value_name = "FROM")]
+    from: StationRef,
+    /// Arrival station (name or UIC reference)
+    #[arg(value_name = "TO")]
+    to: StationRef,
+    /// Departure time: 7:45, now, in 20 min, tomorrow 7:45, ...
+    #[arg(long = "at", value_name = "TIME", allow_hyphen_values = true)]
+    at: Option<String>,
+    /// Departure date: 15.3., 2026-03-15, tomorrow, friday, +1d, ...
+    #[arg(long = "date", value_name = "DATE", allow_hyphen_values = true)]
+    date: Option<String>,
+    /// Search for connections arriving at the given time instead of departing
+    #[arg(long = "arrival")]
+    arrival: bool,
+    /// Which connection of the search results to watch (1 = first)
+    #[arg(long = "pick", value_name = "N", default_value_t = 1)]
+    pick: usize,
+    /// Minimum number of seconds between two refreshes
+    #[arg(long = "interval", value_name = "SECONDS", default_value_t = 30)]
+    interval: u64,
+}
+
+/// Value shown in each cell of the matrix table
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MatrixValueArg {
+    /// Travel time in minutes
+    Duration,
+    /// Number of transfers
+    Transfers,
+}
+
+#[derive(Args)]
+struct MatrixArgs {
+    /// Stations (names or UIC references)
+    #[arg(value_name = "STATION", num_args = 2.., required = true)]
+    stations: Vec<StationRef>,
+    /// Departure time: 7:45, now, in 20 min, tomorrow 7:45, ...
+    #[arg(long = "at", value_name = "TIME", allow_hyphen_values = true)]
+    at: Option<String>,
+    /// Departure date: 15.3., 2026-03-15, tomorrow, friday, +1d, ...
+    #[arg(long = "date", value_name = "DATE", allow_hyphen_values = true)]
+    date: Option<String>,
+    /// Only consider connections departing within this many minutes
+    #[arg(long = "window", value_name = "MINUTES", default_value_t = 60)]
+    window: i64,
+    /// Maximum number of concurrent requests
+    #[arg(long = "concurrency", value_name = "N", default_value_t = 4)]
+    concurrency: usize,
+    /// Value shown in table cells; CSV cells hold both as MINUTES/TRANSFERS
+    #[arg(long = "value", value_name = "VALUE", value_enum, default_value = "duration")]
+    value: MatrixValueArg,
+}
+
+#[derive(Args)]
+struct MeetArgs {
+    /// Candidate meeting stations (names or UIC references)
+    #[arg(value_name = "STATION", required = true)]
+    candidates: Vec<StationRef>,
+    /// Where a traveller starts; repeat once per traveller
+    #[arg(long = "from", value_name = "ORIGIN", required = true)]
+    origins: Vec<StationRef>,
+    /// Time to meet by: 12:00, in 2 h, friday 12:00, ...
+    #[arg(long = "at", value_name = "TIME", allow_hyphen_values = true)]
+    at: Option<String>,
+    /// Date to meet on: 15.3., 2026-03-15, tomorrow, friday, +1d, ...
+    #[arg(long = "date", value_name = "DATE", allow_hyphen_values = true)]
+    date: Option<String>,
+    /// Minutes to arrive before the meeting time
+    #[arg(long = "buffer", value_name = "MIN", default_value_t = 0)]
+    buffer: u32,
+    /// Maximum number of concurrent requests
+    #[arg(long = "concurrency", value_name = "N", default_value_t = 4)]
+    concurrency: usize,
+}
+
+#[derive(Args)]
+struct FormationArgs {
+    /// Train number (e.g. 519 for IC 5 519)
+    #[arg(value_name = "TRAIN")]
+    train: String,
+    /// Date of the ride (15.3., 2026-03-15, tomorrow, ...; default: today)
+    #[arg(long = "date", value_name = "DATE", allow_hyphen_values = true)]
+    date: Option<String>,
+    /// Station at which to show the sectors (UIC reference, default: origin)
+    #[arg(long = "station", value_name = "UIC")]
+    station: Option<String>,
+}
+
+#[derive(Args)]
+struct DisruptionsArgs {
+    /// Only show disruptions affecting this station (UIC reference)
+    #[arg(long = "station", value_name = "UIC")]
+    station: Option<String>,
+    /// Only show disruptions affecting this line (e.g. "IC 5")
+    #[arg(long = "line", value_name = "LINE")]
+    line: Option<String>,
+}
+
+macro_rules! debug {
+    ($enabled:expr, $($arg:tt)*) => {
+        if $enabled {
+            eprintln!("[debug] {}", format!($($arg)*));
+        }
+    };
+}
+
+/// Calculate delay in minutes between aimed and expected time
+fn calculate_delay_minutes(time_aimed: &Option<String>, time_expected: &Option<String>) -> Option<i64> {
+    let aimed = time_aimed.as_ref()?;
+    let expected = time_expected.as_ref()?;
+
+    // Parse ISO 8601 datetime strings (e.g., "2025-02-22T10:32:00+01:00")
+    let aimed_dt = DateTime::parse_from_rfc3339(aimed).ok()?;
+    let expected_dt = DateTime::parse_from_rfc3339(expected).ok()?;
+
+    let diff = expected_dt.signed_duration_since(aimed_dt);
+    let diff_minutes = diff.num_minutes();
+
+    if diff_minutes > 0 {
+        Some(diff_minutes)
+    } else {
+        None
+    }
+}
+
+/// Format delay as a string (e.g., "+5 min" or empty string if on time)
+fn format_delay(time_aimed: &Option<String>, time_expected: &Option<String>) -> String {
+    if let Some(delay) = calculate_delay_minutes(time_aimed, time_expected) {
+        if delay > 0 {
+            return format!(" +{} min", delay);
+        }
+    }
+    String::new()
+}
+
+/// Parse datetime from ISO string to get just the time portion
+fn parse_iso_time(iso_str: &str) -> Option<NaiveTime> {
+    DateTime::parse_from_rfc3339(iso_str)
+        .ok()
+        .map(|dt| dt.time())
+}
+
+/// Format time with optional delay indicator
+fn format_time_with_delay(
+    time_aimed: &Option<String>,
+    time_expected: &Option<String>,
+) -> String {
+    let delay_str = format_delay(time_aimed, time_expected);
+
+    if let Some(aimed) = time_aimed {
+        if let Some(time) = parse_iso_time(aimed) {
+            return format!("{}{}", time.format("%H:%M"), delay_str);
+        }
+    }
+
+    // Fallback to display_time if available (but this doesn't have delay info)
+    "?".to_string()
+}
+
+/// Format a transfer count: "direct", "1 transfer" or "N transfers"
+fn format_transfers(transfers: usize) -> String {
+    match transfers {
+        0 => "direct".to_string(),
+        1 => "1 transfer".to_string(),
+        n => format!("{} transfers", n),
+    }
+}
+
+/// Format a real-time event as a banner line: red for disruptive events, dimmed for information
+fn format_disruption(d: &Disruption) -> String {
+    if d.rt_type.is_disruptive() {
+        format!("⚠ {}", d).bold().red().to_string()
+    } else {
+        format!("ℹ {}", d).dimmed().to_string()
+    }
+}
+
+/// Short label of a coach for the platform diagram, e.g. "1", "2B" or "===" for a locomotive
+fn coach_label(coach: &sbb_api::models::formation::Coach) -> String {
+    if coach.closed {
+        return " x ".to_string();
+    }
+    if coach.features.contains(&CoachFeature::Locomotive) {
+        return "===".to_string();
+    }
+    let mut label: String = coach
+        .travel_classes
+        .iter()
+        .map(|c| match c {
+            TravelClass::First => '1',
+            TravelClass::Second => '2',
+        })
+        .collect();
+    for feature in &coach.features {
+        let c = match feature {
+            CoachFeature::Restaurant => 'R',
+            CoachFeature::Bike => 'B',
+            CoachFeature::Family => 'F',
+            CoachFeature::Wheelchair => 'W',
+            CoachFeature::Sleeper => 'S',
+            CoachFeature::Quiet => 'Q',
+            CoachFeature::Luggage => 'L',
+            CoachFeature::Locomotive | CoachFeature::Unknown => continue,
+        };
+        label.push(c);
+    }
+    label.chars().take(4).collect()
+}
+
+/// Render a train composition as an ASCII platform diagram: a sector line above the coaches
+fn render_formation(formation: &Formation) -> Vec<String> {
+    const CELL: usize = 6;
+
+    let mut sectors = String::new();
+    let mut coaches = String::new();
+    let mut last_sector: Option<&str> = None;
+    for coach in &formation.coaches {
+        let sector = coach.sector.as_deref();
+        if sector.is_some() && sector != last_sector {
+            // Pad the sector line up to the start of this coach
+            let width = coaches.chars().count();
+            while sectors.chars().count() < width {
+                sectors.push(' ');
+            }
+            sectors.push_str(sector.unwrap_or_default());
+            last_sector = sector;
+        }
+        coaches.push_str(&format!("[{:^w$}]", coach_label(coach), w = CELL - 2));
+    }
+
+    vec![
+        format!("Sector  {}", sectors.trim_end()),
+        format!("        {}", coaches),
+        "        1/2 class  R restaurant  B bikes  F family  W wheelchair  S sleeper  Q quiet  L luggage  === locomotive  x closed".to_string(),
+    ]
+}
+
+/// Render flattened rows in one of the tabular formats
+fn render_rows<R: FlatRecord>(rows: &[R], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Csv => flat::to_csv(rows),
+        OutputFormat::Ndjson => flat::to_ndjson(rows),
+        OutputFormat::Markdown => flat::to_markdown(rows),
+        _ => flat::to_table(rows),
+    }
+}
+
+/// Fetch the cheapest price of every trip concurrently. Failed lookups yield `None`.
+async fn fetch_cheapest_prices(
+    trips: &[sbb_api::models::trip::Trip],
+    options: &TravellerOptions,
+    language: Option<Language>,
+    dbg: bool,
+) -> Vec<Option<Price>> {
+    let lookups = trips.iter().map(|trip| async move {
+        match timeout(request_timeout(), get_offers(trip, options, language)).await {
+            Ok(Ok(offers)) => offers.cheapest().and_then(|o| o.price.clone()),
+            Ok(Err(e)) => {
+                debug!(dbg, "price lookup failed for {}: {}", trip.meta.id, e);
+                None
+            }
+            Err(_) => {
+                debug!(dbg, "price lookup timed out for {}", trip.meta.id);
+                None
+            }
+        }
+    });
+    futures::future::join_all(lookups).await
+}
+
+/// Combine `--date` and `--at` relative to the current time, exiting on invalid input
+fn parse_date_time(date: Option<&str>, at: Option<&str>) -> NaiveDateTime {
+    let input: Vec<&str> = [date, at].into_iter().flatten().collect();
+    datetime::parse_date_time(&input.join(" "), Local::now().naive_local()).unwrap_or_else(|e| {
+        print_error_simple(&e.to_string());
+        std::process::exit(EXIT_ERROR);
+    })
+}
+
+/// Resolve coordinates to the nearest stop, exiting when there is none within `NEARBY_RADIUS_M`
+async fn resolve_nearest_stop(coords: &Coordinates, language: Option<Language>, dbg: bool) -> NearbyPlace {
+    debug!(dbg, "Resolving nearest stop to {},{}", coords.latitude, coords.longitude);
+    let nearby = request_or_exit(nearby_places(coords.latitude, coords.longitude, NEARBY_RADIUS_M, language)).await;
+    for n in &nearby {
+        debug!(dbg, "  {:>6.0} m  {} ({:?})", n.distance_m, n.place.display_name, n.place.identifier);
+    }
+    match nearby.into_iter().next() {
+        Some(n) => n,
+        None => {
+            print_error_simple(&format!(
+                "no stop found within {} m of {},{}",
+                NEARBY_RADIUS_M, coords.latitude, coords.longitude
+            ));
+            std::process::exit(EXIT_NO_RESULTS);
+        }
+    }
+}
+
+/// `sbb formation`: print the composition of a train
+async fn run_formation(args: &FormationArgs, json: bool, language: Option<Language>, dbg: bool) {
+    let date = parse_date_time(args.date.as_deref(), None).date();
+    debug!(dbg, "Formation of train {} on {} at {:?}", args.train, date, args.station);
+
+    let formation = request_or_exit(get_formation(&args.train, &date, args.station.as_deref(), language)).await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&formation).expect("Failed to serialize JSON"));
+        std::process::exit(EXIT_SUCCESS);
+    }
+
+    if formation.coaches.is_empty() {
+        println!("{}", "No formation available for this train.".yellow());
+        std::process::exit(EXIT_NO_RESULTS);
+    }
+
+    println!(
+        "{} {}",
+        format!("Train {}", args.train).bold().cyan(),
+        formation.stop_place_name.as_deref().map(|s| format!("at {}", s)).unwrap_or_default(),
+    );
+    println!();
+    for line in render_formation(&formation) {
+        println!("{}", line);
+    }
+
+    std::process::exit(EXIT_SUCCESS);
+}
+
+/// `sbb matrix`: print travel times between every pair of stations
+async fn run_matrix(args: &MatrixArgs, format: OutputFormat, language: Option<Language>, dbg: bool) {
+    let naive_dt = parse_date_time(args.date.as_deref(), args.at.as_deref());
+    let start = Utc.from_utc_datetime(&naive_dt);
+    let end = start + chrono::Duration::minutes(args.window);
+    let options = ReachabilityOptions {
+        max_concurrency: args.concurrency,
+        language,
+        ..Default::default()
+    };
+    let n = args.stations.len();
+    debug!(dbg, "Matrix of {} stations ({} searches) from {} to {}", n, n * (n - 1), start, end);
+
+    let cache = ConnectionCache::new();
+    let start_time = Instant::now();
+    let matrix = travel_time_matrix(&args.stations, start, end, &options, &cache).await;
+    debug!(dbg, "{} searches done in {:.2?}", cache.len(), start_time.elapsed());
+
+    for (i, j, err) in &matrix.errors {
+        print_error_simple(&format!("{} → {}: {}", matrix.stations[*i], matrix.stations[*j], err));
+    }
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&matrix).expect("Failed to serialize JSON"));
+        }
+        OutputFormat::Csv => print!("{}", matrix.to_csv()),
+        OutputFormat::Geojson | OutputFormat::Ics | OutputFormat::Ndjson | OutputFormat::Markdown => {
+            print_error_simple("only text, table, csv and json output are supported for matrices");
+            std::process::exit(EXIT_ERROR);
+        }
+        OutputFormat::Text | OutputFormat::Table => {
+            let names: Vec<String> = matrix.stations.iter().map(|s| s.to_string()).collect();
+            let width = names.iter().map(|n| n.chars().count()).max().unwrap_or(0).max(6);
+            let header: String = names.iter().map(|n| format!("  {:>w$}", n, w = width)).collect();
+            println!("{:w$}{}", "", header.bold(), w = width);
+            for (i, name) in names.iter().enumerate() {
+                let cells: String = (0..n)
+                    .map(|j| {
+                        let text = match (matrix.get(i, j), args.value) {
+                            (Some(c), MatrixValueArg::Duration) => format!("{} min", c.duration_minutes),
+                            (Some(c), MatrixValueArg::Transfers) => c.transfers.to_string(),
+                            (None, _) if i == j => "·".to_string(),
+                            (None, _) => "–".to_string(),
+                        };
+                        format!("  {:>w$}", text, w = width)
+                    })
+                    .collect();
+                println!("{:w$}{}", name.bold(), cells, w = width);
+            }
+        }
+    }
+
+    let exit_code = if matrix.errors.is_empty() { EXIT_SUCCESS } else { EXIT_ERROR };
+    std::process::exit(exit_code);
+}
+
+/// `sbb meet`: rank candidate meeting stations by the travel time from every origin
+async fn run_meet(args: &MeetArgs, format: OutputFormat, language: Option<Language>, dbg: bool) {
+    let deadline = parse_date_time(args.date.as_deref(), args.at.as_deref());
+    let options = ArriveByOptions {
+        buffer_minutes: i64::from(args.buffer),
+        language,
+        ..Default::default()
+    };
+
+    debug!(
+        dbg,
+        "Meeting of {} origins at {} candidates by {} ({} searches)",
+        args.origins.len(),
+        args.candidates.len(),
+        deadline,
+        args.origins.len() * args.candidates.len()
+    );
+    let start_time = Instant::now();
+    let points = find_meeting_point(&args.origins, &args.candidates, deadline, &options, args.concurrency).await;
+    debug!(dbg, "Searches done in {:.2?}", start_time.elapsed());
+
+    let mut failed = false;
+    for candidate in &points.candidates {
+        for leg in &candidate.legs {
+            if let Some(e) = &leg.error {
+                print_error_simple(&format!("{} → {}: {}", leg.origin, candidate.station, e));
+                failed = true;
+            }
+        }
+    }
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&points).expect("Failed to serialize JSON"));
+        }
+        OutputFormat::Csv => print!("{}", points.to_csv()),
+        OutputFormat::Geojson | OutputFormat::Ics | OutputFormat::Ndjson | OutputFormat::Markdown => {
+            print_error_simple("only text, table, csv and json output are supported for meeting points");
+            std::process::exit(EXIT_ERROR);
+        }
+        OutputFormat::Text | OutputFormat::Table => {
+            let width = points.origins.iter().map(|o| o.to_string().chars().count()).max().unwrap_or(0);
+            println!("Meeting by {}", deadline.format("%a %d.%m. %H:%M").to_string().bold());
+            for (i, candidate) in points.candidates.iter().enumerate() {
+                let summary = match (candidate.max_minutes, candidate.total_minutes) {
+                    (Some(max), Some(total)) => format!("longest {} min, total {} min", max, total),
+                    _ => "not reachable by everyone".yellow().to_string(),
+                };
+                println!();
+                println!("{}. {}  {}", i + 1, candidate.station.to_string().bold().white(), summary);
+                for leg in &candidate.legs {
+                    let origin = format!("{:w$}", leg.origin.to_string(), w = width);
+                    let detail = match (&leg.error, leg.duration_minutes) {
+                        (Some(e), _) => format!("search failed: {}", e).red().to_string(),
+                        (None, None) => "no connection in time".yellow().to_string(),
+                        (None, Some(0)) if leg.departure.is_none() => "already there".to_string(),
+                        (None, Some(minutes)) => format!(
+                            "{} → {}  {} min  {}",
+                            format_clock_time(leg.departure).bold().green(),
+                            format_clock_time(leg.arrival).bold().green(),
+                            minutes,
+                            format_transfers(leg.transfers.unwrap_or(0))
+                        ),
+                    };
+                    println!("   {}  {}", origin, detail);
+                }
+            }
+        }
+    }
+
+    let exit_code = if failed {
+        EXIT_ERROR
+    } else if !points.candidates.iter().any(|c| c.is_reachable()) {
+        EXIT_NO_RESULTS
+    } else {
+        EXIT_SUCCESS
+    };
+    std::process::exit(exit_code);
+}
+
+/// `sbb watch`: follow one connection and print every change until it arrives
+async fn run_watch(args: &WatchArgs, json: bool, language: Option<Language>, dbg: bool) {
+    let naive_dt = parse_date_time(args.date.as_deref(), args.at.as_deref());
+    let utc_dt = Utc.from_utc_datetime(&naive_dt);
+    let dt_type = if args.arrival { SearchDateTimeType::Arrival } else { SearchDateTimeType::Departure };
+    let request = get_connections(
+        &args.from.name,
+        args.from.reference.as_deref(),
+        &args.to.name,
+        args.to.reference.as_deref(),
+        &utc_dt,
+        dt_type,
+        language,
+    );
+    let resp = match timeout(request_timeout(), request).await {
+        Ok(Ok(r)) => r,
+        Ok(Err(e)) => exit_with_error(convert_error(e)),
+        Err(_) => exit_with_error(AppError::Timeout),
+    };
+
+    let trip = match args.pick.checked_sub(1).and_then(|i| resp.trips.get(i)) {
+        Some(t) => t.clone(),
+        None => {
+            print_error_simple(&format!("no connection #{} ({} found)", args.pick, resp.trips.len()));
+            std::process::exit(EXIT_NO_RESULTS);
+        }
+    };
+
+    let summary = &trip.summary;
+    if !json {
+        println!(
+            "{} {} {} → {} {}  {}",
+            "Watching".bold().cyan(),
+            summary.departure_anchor.display_time.bold().green(),
+            summary.departure_display_name,
+            summary.arrival_anchor.display_time.bold().green(),
+            summary.arrival_display_name,
+            summary
+                .departure_anchor
+                .transport_designation
+                .as_ref()
+                .map(|t| t.to_string())
+                .unwrap_or_default()
+                .bold()
+                .cyan(),
+        );
+        if trip.is_cancelled() {
+            println!("   {}", "CANCELLED — this connection will not run as planned".bold().white().on_red());
+        }
+    }
+
+    let options = WatchOptions {
+        min_interval: Duration::from_secs(args.interval),
+        language,
+        ..Default::default()
+    };
+    debug!(dbg, "Watching trip {} ({:?})", trip.meta.id, options);
+
+    let mut events = Box::pin(watch_trip(trip, options));
+    while let Some(event) = events.next().await {
+        let now = Local::now().format("%H:%M:%S").to_string();
+        match event {
+            Err(e) => print_error_simple(&format!("refresh failed: {}", e)),
+            Ok(event) if json => {
+                println!("{}", serde_json::to_string(&event).expect("Failed to serialize JSON"));
+            }
+            Ok(WatchEvent::Changed { changes, .. }) => {
+                for change in changes {
+                    println!("{} {}", format!("[{}]", now).dimmed(), change);
+                }
+            }
+            Ok(WatchEvent::Missing) => {
+                println!("{} {}", format!("[{}]", now).dimmed(), "Connection no longer found in the timetable".yellow());
+            }
+            Ok(WatchEvent::Arrived { .. }) => {
+                println!("{} {}", format!("[{}]", now).dimmed(), "Arrived".green());
+            }
+        }
+    }
+
+    std::process::exit(EXIT_SUCCESS);
+}
+
+/// `sbb board`: print the departures or arrivals at a station
+async fn run_board(args: &BoardArgs, json: bool, language: Option<Language>, dbg: bool) {
+    let naive_dt = parse_date_time(args.date.as_deref(), args.at.as_deref());
+    let utc_dt = Utc.from_utc_datetime(&naive_dt);
+    let dt_type = if args.arrivals { SearchDateTimeType::Arrival } else { SearchDateTimeType::Departure };
+    debug!(dbg, "Station board for {:?} at {} ({})", args.station, naive_dt, dt_type);
+
+    let mut board = request_or_exit(get_station_board(&args.station, &utc_dt, dt_type, language)).await;
+    board.entries.truncate(args.limit);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&board).expect("Failed to serialize JSON"));
+        std::process::exit(EXIT_SUCCESS);
+    }
+
+    let title = if args.arrivals { "Arrivals" } else { "Departures" };
+    let station = board.place_name.clone().unwrap_or_else(|| args.station.to_string());
+    println!("{} {}", title.bold().cyan(), station.bold());
+    println!();
+
+    if board.entries.is_empty() {
+        println!("{}", "No entries found for this station and time.".yellow());
+        std::process::exit(EXIT_NO_RESULTS);
+    }
+
+    for entry in &board.entries {
+        let time = format_time_with_delay(
+            &entry.time.as_ref().and_then(|t| t.time_aimed.clone()),
+            &entry.time.as_ref().and_then(|t| t.time_expected.clone()),
+        );
+        let line = entry.transport_designation.as_ref().map(|t| t.to_string()).unwrap_or_default();
+        let track = entry
+            .quay
+            .as_ref()
+            .filter(|q| !q.name.is_empty())
+            .map(|q| {
+                if q.changed {
+                    format!("Pl. {}!", q.name).bold().red()
+                } else {
+                    format!("Pl. {}", q.name).bold().yellow()
+                }
+            })
+            .unwrap_or_default();
+        let direction = entry.direction.clone().unwrap_or_default();
+        if entry.is_cancelled() {
+            println!("{}  {:<8} {}  {}", time, line.bold().cyan(), direction.strikethrough(), "CANCELLED".bold().red());
+        } else {
+            println!("{}  {:<8} {}  {}", time, line.bold().cyan(), direction, track);
+        }
+    }
+
+    std::process::exit(EXIT_SUCCESS);
+}
+
+/// `sbb place`: search places by name or list stops near coordinates
+async fn run_place(args: &PlaceArgs, json: bool, language: Option<Language>, dbg: bool) {
+    // Name searches have no reference point, so their places come without a distance
+    let places: Vec<(Place, Option<f64>)> = match (&args.near, &args.query) {
+        (Some(c), _) => {
+            debug!(dbg, "Stops within {} m of {},{}", args.radius, c.latitude, c.longitude);
+            let nearby = request_or_exit(nearby_places(c.latitude, c.longitude, args.radius, language)).await;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&nearby).expect("Failed to serialize JSON"));
+                std::process::exit(EXIT_SUCCESS);
+            }
+            nearby.into_iter().map(|n| (n.place, Some(n.distance_m))).collect()
+        }
+        (None, Some(query)) => {
+            debug!(dbg, "Places matching {:?}", query);
+            let found = request_or_exit(get_places(query, language)).await;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&found).expect("Failed to serialize JSON"));
+                std::process::exit(EXIT_SUCCESS);
+            }
+            found.into_iter().map(|place| (place, None)).collect()
+        }
+        (None, None) => unreachable!("clap requires QUERY or --near"),
+    };
+
+    if places.is_empty() {
+        println!("{}", "No places found.".yellow());
+        std::process::exit(EXIT_NO_RESULTS);
+    }
+
+    for (place, distance_m) in &places {
+        let reference = place.identifier.as_deref().map(|r| format!("  {}", r)).unwrap_or_default();
+        let distance = distance_m.map(|d| format!("{:>6.0} m  ", d)).unwrap_or_default();
+        println!(
+            "{}{}{}  {}",
+            distance.dimmed(),
+            place.display_name.bold(),
+            reference.cyan(),
+            place.place_type.to_lowercase().replace('_', " ").dimmed(),
+        );
+    }
+
+    std::process::exit(EXIT_SUCCESS);
+}
+
+/// `sbb disruptions`: print the current disruption feed
+async fn run_disruptions(args: &DisruptionsArgs, json: bool, language: Option<Language>, dbg: bool) {
+    let filter = DisruptionFilter {
+        station: args.station.clone(),
+        line: args.line.clone(),
+        ..Default::default()
+    };
+    debug!(dbg, "Disruption filter: {:?}", filter);
+
+    let messages = request_or_exit(get_disruptions(&filter, language)).await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&messages).expect("Failed to serialize JSON"));
+        std::process::exit(EXIT_SUCCESS);
+    }
+
+    println!("{}", "SBB Disruptions".bold().cyan());
+    println!();
+
+    if messages.is_empty() {
+        println!("{}", "No current disruptions.".green());
+        std::process::exit(EXIT_NO_RESULTS);
+    }
+
+    for msg in &messages {
+        let disruptive = msg.rt_type.as_ref().map(|t| t.is_disruptive()).unwrap_or(true);
+        if disruptive {
+            println!("{}", format!("⚠ {}", msg.title).bold().red());
+        } else {
+            println!("{}", format!("ℹ {}", msg.title).bold());
+        }
+        if !msg.affected_lines.is_empty() {
+            println!("   {} {}", "Lines:".bold().white(), msg.affected_lines.join(", "));
+        }
+        if !msg.affected_stations.is_empty() {
+            let stations: Vec<&str> = msg.affected_stations.iter().map(|s| s.display_name.as_str()).collect();
+            println!("   {} {}", "Stations:".bold().white(), stations.join(", "));
+        }
+        for period in &msg.validity_periods {
+            let fmt = |t: &Option<String>| {
+                t.as_deref()
+                    .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                    .map(|t| t.format("%d.%m. %H:%M").to_string())
+                    .unwrap_or_else(|| "…".to_string())
+            };
+            println!("   {} {} – {}", "Valid:".bold().white(), fmt(&period.start), fmt(&period.end));
+        }
+        if let Some(text) = &msg.text {
+            for line in text.lines() {
+                println!("   {}", line.dimmed());
+            }
+        }
+        println!();
+    }
+
+    std::process::exit(EXIT_SUCCESS);
+}
+
+/// `sbb trip --days N`: the first suitable connection at the same time on several days
+#[allow(clippy::too_many_arguments)]
+async fn run_overview(
+    args: &TripArgs,
+    from: &StationRef,
+    to: &StationRef,
+    start: NaiveDateTime,
+    dt_type: SearchDateTimeType,
+    filter: &TripFilter,
+    format: OutputFormat,
+    language: Option<Language>,
+    dbg: bool,
+) -> ! {
+    let first = start.date();
+    let days = match (args.days, &args.until) {
+        (Some(n), _) => n,
+        (None, Some(until)) => {
+            let last = parse_date_time(Some(until), None).date();
+            if last < first {
+                print_error_simple(&format!("--until {} is before the search date {}", last, first));
+                std::process::exit(EXIT_ERROR);
+            }
+            (last - first).num_days() as usize + 1
+        }
+        (None, None) => unreachable!("only called with --days or --until"),
+    };
+    if days == 0 || days > MAX_DAYS {
+        print_error_simple(&format!("can search between 1 and {} days, not {}", MAX_DAYS, days));
+        std::process::exit(EXIT_ERROR);
+    }
+
+    let dates: Vec<NaiveDate> = first.iter_days().take(days).collect();
+    let concurrency = args.concurrency.unwrap_or(4);
+    debug!(dbg, "Overview of {} days from {} at {} ({} concurrent)", days, first, start.time(), concurrency);
+    let start_time = Instant::now();
+    let summaries = multi_day_overview(from, to, &dates, start.time(), dt_type, filter, concurrency, language).await;
+    debug!(dbg, "{} searches done in {:.2?}", days, start_time.elapsed());
+
+    let trips: Vec<Trip> = summaries.iter().filter_map(|d| d.trip.clone()).collect();
+    let exit_code = if summaries.iter().any(|d| d.error.is_some()) {
+        EXIT_ERROR
+    } else if trips.is_empty() {
+        EXIT_NO_RESULTS
+    } else {
+        EXIT_SUCCESS
+    };
+
+    if let Some(template) = &args.template {
+        for trip in &trips {
+            println!("{}", template.render(trip));
+        }
+        std::process::exit(exit_code);
+    }
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&summaries).expect("Failed to serialize JSON"));
+        }
+        OutputFormat::Geojson => {
+            let geojson = trips_to_geojson(&trips);
+            println!("{}", serde_json::to_string_pretty(&geojson).expect("Failed to serialize GeoJSON"));
+        }
+        OutputFormat::Csv | OutputFormat::Ndjson | OutputFormat::Markdown | OutputFormat::Table => {
+            print!("{}", render_rows(&summaries, format));
+        }
+        OutputFormat::Ics => {
+            let events = if args.per_leg { IcsEvents::PerLeg } else { IcsEvents::PerTrip };
+            print!("{}", trips_to_ics(&trips, events));
+        }
+        OutputFormat::Text => {
+            for day in &summaries {
+                let date = day.date.format("%a %d.%m.").to_string();
+                let trip = match (&day.error, &day.trip) {
+                    (Some(e), _) => {
+                        println!("{}  {}", date.bold().white(), format!("search failed: {}", e).red());
+                        continue;
+                    }
+                    (None, None) => {
+                        println!("{}  {}", date.bold().white(), "no suitable connection".yellow());
+                        continue;
+                    }
+                    (None, Some(trip)) => trip,
+                };
+                println!(
+                    "{}  {} → {}  {}  {}  {}",
+                    date.bold().white(),
+                    format_clock_time(day.departure).bold().green(),
+                    format_clock_time(day.arrival).bold().green(),
+                    day.duration_minutes.map(|m| format!("{} min", m)).unwrap_or_default().yellow(),
+                    format_transfers(trip.transfers()).dimmed(),
+                    day.line.clone().unwrap_or_default().bold().cyan(),
+                );
+                for d in trip.disruptions() {
+                    println!("             {}", format_disruption(&d));
+                }
+            }
+        }
+    }
+
+    std::process::exit(exit_code);
+}
+
+/// Load one more page of results, warning instead of exiting when it fails.
+/// Returns the number of connections added.
+#[allow(clippy::too_many_arguments)]
+async fn load_more(
+    resp: &mut TripSearchResponse,
+    from: &StationRef,
+    to: &StationRef,
+    on: &DateTime<Utc>,
+    dt_type: SearchDateTimeType,
+    direction: PageDirection,
+    language: Option<Language>,
+    dbg: bool,
+) -> usize {
+    match timeout(request_timeout(), load_page(resp, from, to, on, dt_type, direction, language)).await {
+        Ok(Ok(added)) => {
+            debug!(dbg, "{:?} page: {} new trip(s)", direction, added);
+            added
+        }
+        Ok(Err(e)) => {
+            print_error_simple(&format!("could not load more connections: {}", e));
+            0
+        }
+        Err(_) => {
+            print_error_simple("could not load more connections: request timed out");
+            0
+        }
+    }
+}
+
+/// A value from the config file, exiting with an error if it is invalid
+fn config_value<T: ValueEnum>(key: &str, value: Option<&str>) -> Option<T> {
+    Config::value(key, value).unwrap_or_else(|e| {
+        print_error_simple(&e);
+        std::process::exit(EXIT_ERROR);
+    })
+}
+
+/// Fill in what the command line left open from the config: aliases, class and travelcard
+fn apply_config(args: &mut TripArgs, config: &Config) {
+    for (input, reference) in [(&mut args.from, &mut args.from_ref), (&mut args.to, &mut args.to_ref)] {
+        if let Some(station) = input.as_deref().and_then(|i| config.alias(i)) {
+            *input = Some(station.name).filter(|n| !n.is_empty());
+            if reference.is_none() {
+                *reference = station.reference;
+            }
+        }
+    }
+    args.class = args.class.or_else(|| config_value("class", config.class.as_deref()));
+    args.card = args.card.or_else(|| config_value("card", config.card.as_deref()));
+}
+
+/// The search as typed, for the history
+fn describe_query(args: &TripArgs) -> String {
+    let from = args
+        .from
+        .clone()
+        .or_else(|| args.from_ref.clone())
+        .or_else(|| args.from_coords.as_ref().map(|c| format!("{},{}", c.latitude, c.longitude)))
+        .unwrap_or_default();
+    let to = args.to.clone().or_else(|| args.to_ref.clone()).unwrap_or_default();
+    format!("{} → {}", from, to)
+}
+
+fn history_path() -> PathBuf {
+    History::default_path().unwrap_or_else(|| {
+        print_error_simple("cannot locate the history file: neither XDG_STATE_HOME nor HOME is set");
+        std::process::exit(EXIT_ERROR);
+    })
+}
+
+/// `sbb history`: list past searches, most recent first
+fn run_history(args: &HistoryArgs, json: bool) {
+    let path = history_path();
+    if args.clear {
+        match std::fs::remove_file(&path) {
+            Ok(()) => println!("History cleared."),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => println!("History is already empty."),
+            Err(e) => {
+                print_error_simple(&format!("cannot remove {}: {}", path.display(), e));
+                std::process::exit(EXIT_ERROR);
+            }
+        }
+        std::process::exit(EXIT_SUCCESS);
+    }
+
+    let history = History::load(&path).unwrap_or_else(|e| {
+        print_error_simple(&e);
+        std::process::exit(EXIT_ERROR);
+    });
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&history.entries).expect("Failed to serialize JSON"));
+        std::process::exit(EXIT_SUCCESS);
+    }
+
+    if history.entries.is_empty() {
+        println!("{}", "No searches yet.".yellow());
+        std::process::exit(EXIT_NO_RESULTS);
+    }
+
+    for (i, entry) in history.entries.iter().enumerate() {
+        let when = entry.searched_at.with_timezone(&Local).format("%d.%m.%Y %H:%M").to_string();
+        let resolved = format!("{} → {}", entry.from, entry.to);
+        let resolved = if resolved == entry.query { String::new() } else { format!("  ({})", resolved) };
+        let arrival = if entry.arrival { "  arrival" } else { "" };
+        println!(
+            "{:>3}  {}  {}{}{}",
+            (i + 1).to_string().bold().white(),
+            when.dimmed(),
+            entry.query.bold(),
+            resolved.dimmed(),
+            arrival.yellow(),
+        );
+    }
+
+    std::process::exit(EXIT_SUCCESS);
+}
+
+/// `sbb again`: the trip arguments of a past search
+fn again_args(args: &AgainArgs) -> (TripArgs, String) {
+    let history = History::load(&history_path()).unwrap_or_else(|e| {
+        print_error_simple(&e);
+        std::process::exit(EXIT_ERROR);
+    });
+    let entry = match history.get(args.n) {
+        Some(e) => e,
+        None => {
+            print_error_simple(&format!("no search #{} in the history ({} saved)", args.n, history.entries.len()));
+            std::process::exit(EXIT_NO_RESULTS);
+        }
+    };
+    let trip = TripArgs {
+        from: Some(entry.from.name.clone()).filter(|n| !n.is_empty()),
+        from_ref: entry.from.reference.clone(),
+        to: Some(entry.to.name.clone()).filter(|n| !n.is_empty()),
+        to_ref: entry.to.reference.clone(),
+        arrival: entry.arrival,
+        ..Default::default()
+    };
+    (trip, entry.query.clone())
+}
+
+/// `sbb trip` (or just `sbb FROM TO`): search connections
+async fn run_trip(mut args: TripArgs, format: OutputFormat, recorder: Option<Recorder>, language: Option<Language>, dbg: bool) {
+    // With --from-coords the single positional argument is the destination
+    if args.from_coords.is_some() && args.from.is_some() {
+        if args.to.is_some() || args.to_ref.is_some() {
+            print_error_simple("FROM cannot be combined with --from-coords");
+            std::process::exit(EXIT_ERROR);
+        }
+        args.to = args.from.take();
+    }
+
+    // Validate required arguments: either text or UIC ref must be provided for both from and to
+    let from_input = args.from.is_some() || args.from_ref.is_some() || args.from_coords.is_some();
+    let to_input = args.to.is_some() || args.to_ref.is_some();
+
+    if !from_input {
+        print_error_simple("either FROM, --from-ref or --from-coords must be provided");
+        std::process::exit(EXIT_ERROR);
+    }
+    if !to_input {
+        print_error_simple("either TO or --to-ref must be provided");
+        std::process::exit(EXIT_ERROR);
+    }
+
+    // With --arrive-by, the search time is the deadline
+    let at = args.arrive_by.as_deref().or(args.at.as_deref());
+    let naive_dt = parse_date_time(args.date.as_deref(), at);
+    let (date, time) = (naive_dt.date(), naive_dt.time());
+    let utc_dt = Utc.from_utc_datetime(&naive_dt);
+    // Shortly after midnight, the last connection is still the one of the evening before,
+    // while the first one is this morning's
+    let service_date = if args.last && args.date.is_none() { service_day_of(naive_dt) } else { date };
+
+    let dt_type = if args.arrival {
+        SearchDateTimeType::Arrival
+    } else {
+        SearchDateTimeType::Departure
+    };
+
+    // FROM may itself be given as "LAT,LON"
+    let from_coords = args
+        .from_coords
+        .or_else(|| args.from.as_deref().and_then(|f| f.parse::<Coordinates>().ok()));
+    let (from, from_ref, from_distance) = match &from_coords {
+        Some(c) => {
+            let nearest = resolve_nearest_stop(c, language, dbg).await;
+            (Some(nearest.place.display_name), nearest.place.identifier, Some(nearest.distance_m))
+        }
+        None => (args.from.clone(), args.from_ref.clone(), None),
+    };
+
+    // Machine-readable formats only print the result on stdout
+    if format == OutputFormat::Text && args.template.is_none() {
+        // Print SBB header
+        println!("{}", "SBB Connections".bold().cyan());
+        println!();
+
+        // Print search parameters
+        print!("{} ", "From:".bold().white());
+        print!("{}", from.clone().unwrap_or_default());
+        match (&from_coords, from_distance) {
+            (Some(c), Some(d)) => println!(" {}", format!("({:.0} m from {},{})", d, c.latitude, c.longitude).dimmed()),
+            _ => println!(),
+        }
+        print!("{} ", "To:".bold().white());
+        println!("{}", args.to.clone().unwrap_or_default());
+        if let Some(ref from_ref) = from_ref {
+            println!("{} {}", "From Ref:".bold().white(), from_ref);
+        }
+        if let Some(ref to_ref) = args.to_ref {
+            println!("{} {}", "To Ref:".bold().white(), to_ref);
+        }
+        let when = match (args.first, args.last) {
+            (true, _) => "first connection".to_string(),
+            (_, true) => "last connection".to_string(),
+            _ if args.arrive_by.is_some() && args.buffer > 0 => {
+                format!("arrive by {}, {} min buffer", time.format("%H:%M"), args.buffer)
+            }
+            _ if args.arrive_by.is_some() => format!("arrive by {}", time.format("%H:%M")),
+            _ => time.format("%H:%M").to_string(),
+        };
+        println!("{} {} ({})", "Date:".bold().white(), service_date.format("%Y-%m-%d (%a)"), when);
+        println!();
+    }
+
+    // Enhanced debug output with clear sections
+    debug!(dbg, "----------------------------------------");
+    debug!(dbg, "API endpoint: {}", sbb_api::API_ENDPOINT);
+    debug!(dbg, "----------------------------------------");
+    debug!(dbg, "Search parameters (detailed):");
+    debug!(dbg, "  from:       {:?}", from);
+    debug!(dbg, "  from_ref:   {:?}", from_ref);
+    debug!(dbg, "  to:         {:?}", args.to);
+    debug!(dbg, "  to_ref:     {:?}", args.to_ref);
+    debug!(dbg, "  date:       {}", date);
+    debug!(dbg, "  time:       {}", time);
+    debug!(dbg, "  datetime_type: {}", dt_type);
+    debug!(dbg, "  utc_dt:     {}", utc_dt);
+    debug!(dbg, "----------------------------------------");
+    debug!(dbg, "Connecting to API...");
+
+    let mut filter = TripFilter {
+        max_transfers: if args.direct { Some(0) } else { args.max_transfers },
+        excluded_stations: args.exclude.clone(),
+        ..Default::default()
+    };
+    for min_change in &args.min_change {
+        match min_change {
+            MinChangeArg::Global(minutes) => filter.min_change_minutes = Some(*minutes),
+            MinChangeArg::At(station, minutes) => filter.min_change_at.push((station.clone(), *minutes)),
+        }
+    }
+
+    let from_station = StationRef::new(from.as_deref().unwrap_or(""), from_ref.as_deref());
+    let to_station = StationRef::new(args.to.as_deref().unwrap_or(""), args.to_ref.as_deref());
+
+    if args.days.is_some() || args.until.is_some() {
+        run_overview(&args, &from_station, &to_station, naive_dt, dt_type, &filter, format, language, dbg).await;
+    }
+
+    let start_time = Instant::now();
+
+    if args.first || args.last {
+        debug!(dbg, "{} connection of the service day {}", if args.first { "First" } else { "Last" }, service_date);
+    }
+    let arrive_by = ArriveByOptions {
+        buffer_minutes: i64::from(args.buffer),
+        filter: filter.clone(),
+        language,
+        ..Default::default()
+    };
+    if args.arrive_by.is_some() {
+        debug!(dbg, "Arrive by {} ({:?})", naive_dt, arrive_by);
+    }
+    // --first, --last and --arrive-by pick a single connection
+    let request = async {
+        if args.first || args.last || args.arrive_by.is_some() {
+            let trip = if args.first {
+                first_connection(&from_station, &to_station, service_date, &filter, language).await?
+            } else if args.last {
+                last_connection(&from_station, &to_station, service_date, &filter, language).await?
+            } else {
+                plan_arrive_by(&from_station, &to_station, naive_dt, &arrive_by).await?
+            };
+            Ok(TripSearchResponse {
+                trips: trip.into_iter().collect(),
+                earlier_paging_cursor: None,
+                later_paging_cursor: None,
+            })
+        } else {
+            get_connections(
+                from.as_deref().unwrap_or(""),
+                from_ref.as_deref(),
+                args.to.as_deref().unwrap_or(""),
+                args.to_ref.as_deref(),
+                &utc_dt,
+                dt_type,
+                language,
+            )
+            .await
+        }
+    };
+
+    let mut resp = match timeout(request_timeout(), request).await {
+        Ok(Ok(r)) => {
+            let elapsed = start_time.elapsed();
+            debug!(dbg, "Response received in {:.2?}", elapsed);
+            debug!(dbg, "Found {} trip(s)", r.trips.len());
+            r
+        }
+        Ok(Err(e)) => exit_with_error(convert_error(e)),
+        Err(_) => exit_with_error(AppError::Timeout),
+    };
+
+    if let Some(recorder) = &recorder {
+        if let Err(e) = recorder.record(from_station.clone(), to_station.clone(), args.arrival, &resp) {
+            debug!(dbg, "search not saved to history: {}", e);
+        }
+    }
+
+    let pages = std::iter::repeat_n(PageDirection::Earlier, args.earlier as usize)
+        .chain(std::iter::repeat_n(PageDirection::Later, args.later as usize));
+    for direction in pages {
+        load_more(&mut resp, &from_station, &to_station, &utc_dt, dt_type, direction, language, dbg).await;
+    }
+    if let Some(count) = args.count {
+        for _ in 0..MAX_PAGES {
+            if resp.trips.iter().filter(|t| filter.matches(t)).count() >= count {
+                break;
+            }
+            if load_more(&mut resp, &from_station, &to_station, &utc_dt, dt_type, PageDirection::Later, language, dbg).await == 0 {
+                break;
+            }
+        }
+    }
+
+    let elapsed = start_time.elapsed();
+
+    let before = resp.trips.len();
+    filter.apply(&mut resp.trips);
+    debug!(dbg, "Filter kept {} of {} trip(s)", resp.trips.len(), before);
+    if let Some(count) = args.count {
+        resp.trips.truncate(count);
+    }
+
+    let criteria: Vec<Criterion> = args.sort.iter().map(|&c| c.into()).collect();
+    if args.pareto {
+        let pareto_criteria = if criteria.is_empty() {
+            vec![Criterion::Duration, Criterion::Transfers, Criterion::Arrival]
+        } else {
+            criteria.clone()
+        };
+        let before = resp.trips.len();
+        retain_pareto_front(&mut resp.trips, &pareto_criteria);
+        debug!(dbg, "Pareto filter kept {} of {} trip(s)", resp.trips.len(), before);
+    }
+    if !criteria.is_empty() {
+        sort_trips(&mut resp.trips, &criteria);
+    }
+    if let Some(pick) = args.pick {
+        match pick.checked_sub(1).filter(|&i| i < resp.trips.len()) {
+            Some(i) => resp.trips = vec![resp.trips.swap_remove(i)],
+            None => {
+                print_error_simple(&format!("no connection #{} ({} found)", pick, resp.trips.len()));
+                std::process::exit(EXIT_NO_RESULTS);
+            }
+        }
+    }
+
+    if let Some(template) = &args.template {
+        for trip in &resp.trips {
+            println!("{}", template.render(trip));
+        }
+        let exit_code = if resp.trips.is_empty() { EXIT_NO_RESULTS } else { EXIT_SUCCESS };
+        std::process::exit(exit_code);
+    }
+
+    match format {
+        OutputFormat::Json => {
+            let json_output = serde_json::to_string_pretty(&resp).expect("Failed to serialize JSON");
+            println!("{}", json_output);
+            std::process::exit(EXIT_SUCCESS);
+        }
+        OutputFormat::Geojson => {
+            let geojson = trips_to_geojson(&resp.trips);
+            println!("{}", serde_json::to_string_pretty(&geojson).expect("Failed to serialize GeoJSON"));
+            std::process::exit(EXIT_SUCCESS);
+        }
+        OutputFormat::Csv | OutputFormat::Ndjson | OutputFormat::Markdown | OutputFormat::Table => {
+            if args.per_leg {
+                print!("{}", render_rows(&leg_rows(&resp.trips), format));
+            } else {
+                print!("{}", render_rows(&trip_rows(&resp.trips), format));
+            }
+            std::process::exit(EXIT_SUCCESS);
+        }
+        OutputFormat::Ics => {
+            let events = if args.per_leg { IcsEvents::PerLeg } else { IcsEvents::PerTrip };
+            print!("{}", trips_to_ics(&resp.trips, events));
+            std::process::exit(EXIT_SUCCESS);
+        }
+        OutputFormat::Text => {}
+    }
+
+    // Print results
+    if resp.trips.is_empty() {
+        // This is NOT an error - just no connections found for the given criteria
+        println!("{}", "No connections found for the specified route and time.".yellow());
+        debug!(dbg, "no trips returned from API");
+        std::process::exit(EXIT_NO_RESULTS);
+    }
+
+    let prices = if args.prices {
+        let options = TravellerOptions {
+            travel_class: match args.class.unwrap_or(ClassArg::Second) {
+                ClassArg::First => TravelClass::First,
+                ClassArg::Second => TravelClass::Second,
+            },
+            reduction: match args.card.unwrap_or(CardArg::None) {
+                CardArg::None => Reduction::None,
+                CardArg::HalfFare => Reduction::HalfFare,
+                CardArg::Ga => Reduction::GeneralAbonnement,
+            },
+        };
+        debug!(dbg, "Fetching prices ({} / {})", options.travel_class, options.reduction);
+        fetch_cheapest_prices(&resp.trips, &options, language, dbg).await
+    } else {
+        Vec::new()
+    };
+
+    // Formations keyed by (trip index, leg index)
+    let mut formations: HashMap<(usize, usize), Formation> = HashMap::new();
+    if args.formation {
+        let lookups = resp.trips.iter().enumerate().flat_map(|(i, trip)| {
+            trip.detail.iter().flat_map(move |d| {
+                d.legs.iter().enumerate().filter_map(move |(j, leg)| match leg {
+                    TripLeg::PtRideLeg(pt) if pt.formation_path.is_some() => Some(async move {
+                        let result = timeout(request_timeout(), get_leg_formation(pt, language)).await;
+                        ((i, j), result)
+                    }),
+                    _ => None,
+                })
+            })
+        });
+        for (key, result) in futures::future::join_all(lookups).await {
+            match result {
+                Ok(Ok(f)) => {
+                    formations.insert(key, f);
+                }
+                Ok(Err(e)) => debug!(dbg, "formation lookup failed for {:?}: {}", key, e),
+                Err(_) => debug!(dbg, "formation lookup timed out for {:?}", key),
+            }
+        }
+    }
+
+    // Results header
+    println!("{}", "Connections".bold().underline());
+    println!();
+
+    for (i, trip) in resp.trips.iter().enumerate() {
+        let summary = &trip.summary;
+
+        let transport = summary
+            .departure_anchor
+            .transport_designation
+            .as_ref()
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| "?".to_string());
+
+        let direction = summary
+            .departure_anchor
+            .direction
+            .as_deref()
+            .map(|d| format!(" -> {}", d))
+            .unwrap_or_default();
+
+        let duration_str = summary
+            .duration
+            .as_ref()
+            .map(|d| d.localized_label.as_str())
+            .unwrap_or("?");
+
+        let transfer_text = format_transfers(trip.transfers());
+
+        // Get departure time with delay
+        let dep_time = format_time_with_delay(
+            &summary.departure_anchor.time_aimed,
+            &summary.departure_anchor.time_expected,
+        );
+
+        // Get arrival time with delay
+        let arr_time = format_time_with_delay(
+            &summary.arrival_anchor.time_aimed,
+            &summary.arrival_anchor.time_expected,
+        );
+
+        let price = match prices.get(i) {
+            Some(Some(p)) => format!("  from {}", p).green().to_string(),
+            Some(None) => "  price n/a".dimmed().to_string(),
+            None => String::new(),
+        };
+
+        // Print trip summary line
+        println!(
+            "{}. {} → {}  {}  {}  {}{}{}",
+            (i + 1).to_string().bold().white(),
+            dep_time,
+            arr_time,
+            duration_str.yellow(),
+            transfer_text.dimmed(),
+            transport.bold().cyan(),
+            direction,
+            price,
+        );
+
+        let disruptions = trip.disruptions();
+        if trip.is_cancelled() {
+            println!("   {}", "CANCELLED — this connection will not run as planned".bold().white().on_red());
+        }
+        for d in disruptions.iter().filter(|d| d.leg_index.is_none()) {
+            println!("   {}", format_disruption(d));
+        }
+
+        // Print per-leg stop info if detail is available
+        if let Some(detail) = &trip.detail {
+            for (leg_index, leg) in detail.legs.iter().enumerate() {
+                if let TripLeg::PtRideLeg(pt) = leg {
+                    let dep = &pt.departure_stop_point;
+                    let arr = &pt.arrival_stop_point;
+
+                    // Departure time with delay
+                    let dep_time_leg = format_time_with_delay(
+                        &dep.departure_time.as_ref().and_then(|t| t.time_aimed.clone()),
+                        &dep.departure_time.as_ref().and_then(|t| t.time_expected.clone()),
+                    );
+
+                    // Arrival time with delay
+                    let _arr_time_leg = format_time_with_delay(
+                        &arr.arrival_time.as_ref().and_then(|t| t.time_aimed.clone()),
+                        &arr.arrival_time.as_ref().and_then(|t| t.time_expected.clone()),
+                    );
+
+                    let track = dep
+                        .quay
+                        .as_ref()
+                        .filter(|q| !q.name.is_empty())
+                        .map(|q| {
+                            if q.changed {
+                                format!(" [Platform {}!]", q.name).bold().red()
+                            } else {
+                                format!(" [Platform {}]", q.name).bold().yellow()
+                            }
+                        })
+                        .unwrap_or_default();
+
+                    // Get transport info for this leg
+                    let leg_transport = pt
+                        .first_transport_designation
+                        .as_ref()
+                        .map(|t| t.to_string())
+                        .unwrap_or_else(|| "".to_string());
+
+                    println!(
+                        "      {}  {} → {}  {}{}",
+                        leg_transport.bold().cyan(),
+                        dep.display_name.white(),
+                        arr.display_name.white(),
+                        dep_time_leg.bold().green(),
+                        track,
+                    );
+
+                    // Platform changes are already flagged on the track marker
+                    for d in disruptions
+                        .iter()
+                        .filter(|d| d.leg_index == Some(leg_index) && d.rt_type != RtType::PlatformChange)
+                    {
+                        println!("        {}", format_disruption(d));
+                    }
+
+                    if let Some(formation) = formations.get(&(i, leg_index)) {
+                        for line in render_formation(formation) {
+                            println!("        {}", line.dimmed());
+                        }
+                    }
+                } else if let TripLeg::AccessLeg(access) = leg {
+                    // Walking/transfer legs
+                    let dep_name = access
+                        .departure_stop_point
+                        .as_ref()
+                        .map(|s| s.display_name.as_str())
+                        .unwrap_or("");
+                    let arr_name = access
+                        .arrival_stop_point
+                        .as_ref()
+                        .map(|s| s.display_name.as_str())
+                        .unwrap_or("");
+                    if !dep_name.is_empty() && !arr_name.is_empty() {
+                        println!(
+                            "      {}  {} → {}",
+                            "Walk".italic().dimmed(),
+                            dep_name.white().dimmed(),
+                            arr_name.white().dimmed(),
+                        );
+                    }
+                }
+            }
+        } else {
+            // Fall back to summary anchors when no detail is available
+            let dep = &summary.departure_anchor;
+            let arr = &summary.arrival_anchor;
+            let track = dep
+                .quay
+                .as_ref()
+                .filter(|q| !q.name.is_empty())
+                .map(|q| format!("  [Pl. {}]", q.name).bold().yellow())
+                .unwrap_or_default();
+            println!(
+                "      {} → {}  {}{}",
+                dep.place_name.white(),
+                arr.place_name.white(),
+                dep.display_time.bold().green(),
+                track,
+            );
+        }
+
+        println!();
+    }
+
+    // Print summary line
+    println!(
+        "{} {}",
+        "Total:".bold().white(),
+        format!("{} connection(s) found in {:.2?}", resp.trips.len(), elapsed).dimmed()
+    );
+
+    // Success - exit with code 0
+    std::process::exit(EXIT_SUCCESS);
+}
+
+#[actix_rt::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize colors based on terminal support and --no-color flag
+    init_colors(cli.no_color);
+
+    let dbg = cli.debug;
+    let config = Config::load(cli.config.as_deref()).unwrap_or_else(|e| {
+        print_error_simple(&e);
+        std::process::exit(EXIT_ERROR);
+    });
+    debug!(dbg, "Config: {:?}", config);
+
+    // Command line options take precedence over the config
+    if let Some(secs) = cli.timeout.or(config.timeout) {
+        let _ = REQUEST_TIMEOUT.set(Duration::from_secs(secs));
+    }
+    let language = cli.language.or(config.language);
+    let format = if cli.json {
+        OutputFormat::Json
+    } else {
+        cli.format
+            .or_else(|| config_value("format", config.format.as_deref()))
+            .unwrap_or(OutputFormat::Text)
+    };
+    // Commands other than searches only print text or JSON. Asking them for another format
+    // is an error; a format from the config is meant for searches and falls back to text.
+    let text_or_json = |command: &str| {
+        if cli.format.is_some() && !matches!(format, OutputFormat::Text | OutputFormat::Json) {
+            print_error_simple(&format!("sbb {} only supports text and json output", command));
+            std::process::exit(EXIT_ERROR);
+        }
+        format == OutputFormat::Json
+    };
+
+    // Successful searches are remembered unless disabled in the config
+    let recorder = |query: String| {
+        config
+            .history
+            .unwrap_or(true)
+            .then(History::default_path)
+            .flatten()
+            .map(|path| Recorder { path, query })
+    };
+
+    match cli.command {
+        Some(Command::Trip(mut args)) => {
+            let recorder = recorder(describe_query(&args));
+            apply_config(&mut args, &config);
+            run_trip(*args, format, recorder, language, dbg).await
+        }
+        Some(Command::Board(mut args)) => {
+            args.station = config.resolve_station(args.station);
+            run_board(&args, text_or_json("board"), language, dbg).await
+        }
+        Some(Command::Place(args)) => run_place(&args, text_or_json("place"), language, dbg).await,
+        Some(Command::Tui(args)) => {
+            let refresh = args.refresh.map(Duration::from_secs);
+            let from = args.from.map(|f| config.resolve(&f));
+            let to = args.to.map(|t| config.resolve(&t));
+            if let Err(e) = tui::run(from, to, refresh, language).await {
+                print_error_simple(&format!("terminal error: {}", e));
+                std::process::exit(EXIT_ERROR);
+            }
+            std::process::exit(EXIT_SUCCESS);
+        }
+        Some(Command::Completions(args)) => {
+            let mut cmd = Cli::command();
+            clap_complete::generate(args.shell, &mut cmd, "sbb", &mut std::io::stdout());
+            std::process::exit(EXIT_SUCCESS);
+        }
+        Some(Command::Disruptions(args)) => run_disruptions(&args, text_or_json("disruptions"), language, dbg).await,
+        Some(Command::Formation(args)) => run_formation(&args, text_or_json("formation"), language, dbg).await,
+        Some(Command::Matrix(mut args)) => {
+            args.stations = args.stations.into_iter().map(|s| config.resolve_station(s)).collect();
+            run_matrix(&args, format, language, dbg).await
+        }
+        Some(Command::Meet(mut args)) => {
+            args.origins = args.origins.into_iter().map(|s| config.resolve_station(s)).collect();
+            args.candidates = args.candidates.into_iter().map(|s| config.resolve_station(s)).collect();
+            run_meet(&args, format, language, dbg).await
+        }
+        Some(Command::Watch(mut args)) => {
+            args.from = config.resolve_station(args.from);
+            args.to = config.resolve_station(args.to);
+            run_watch(&args, text_or_json("watch"), language, dbg).await
+        }
+        Some(Command::History(args)) => run_history(&args, text_or_json("history")),
+        Some(Command::Again(args)) => {
+            let (mut args, query) = again_args(&args);
+            apply_config(&mut args, &config);
+            run_trip(args, format, recorder(query), language, dbg).await
+        }
+        None => {
+            let mut args = cli.trip;
+            let recorder = recorder(describe_query(&args));
+            apply_config(&mut args, &config);
+            run_trip(args, format, recorder, language, dbg).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_negative_offsets() {
+        for args in [
+            vec!["sbb", "trip", "Bern", "Basel", "--at", "-2h"],
+            vec!["sbb", "trip", "Bern", "Basel", "--date", "-1d", "--at", "8:00"],
+            vec!["sbb", "board", "Bern", "--at", "-30m"],
+        ] {
+            assert!(Cli::try_parse_from(&args).is_ok(), "{:?}", args);
+        }
+        // Only the time takes offsets, the buffer cannot be negative
+        for buffer in ["5", "-5"] {
+            let trip = Cli::try_parse_from(["sbb", "trip", "Bern", "Basel", "--arrive-by", "9:00", "--buffer", buffer]);
+            let meet = Cli::try_parse_from(["sbb", "meet", "Olten", "--from", "Bern", "--at", "9:00", "--buffer", buffer]);
+            assert_eq!(trip.is_ok(), buffer == "5");
+            assert_eq!(meet.is_ok(), buffer == "5");
+        }
+    }
+
+    #[test]
+    fn test_station_groups() {
+        assert!(Cli::try_parse_from(["sbb", "trip", "--from-ref", "8500010", "--to-ref", "8507000"]).is_ok());
+        assert!(Cli::try_parse_from(["sbb", "trip", "Basel", "--from-ref", "8500010", "--to-ref", "8507000"]).is_err());
+        assert!(Cli::try_parse_from(["sbb", "Basel", "Bern", "--to-ref", "8507000"]).is_err());
+    }
+
+    #[test]
+    fn test_render_formation() {
+        let formation: Formation = serde_json::from_str(
+            r#"{
+                "sectors": ["A", "B", "C"],
+                "coaches": [
+                    {"features": ["LOCOMOTIVE"], "sector": "A"},
+                    {"travelClasses": ["FIRST"], "sector": "A"},
+                    {"travelClasses": ["SECOND"], "sector": "B", "features": ["RESTAURANT"]},
+                    {"travelClasses": ["SECOND"], "sector": "C", "features": ["BIKE"], "closed": true}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let lines = render_formation(&formation);
+        assert_eq!(lines[0], "Sector  A           B     C");
+        assert_eq!(lines[1], "        [=== ][ 1  ][ 2R ][ x  ]");
+    }
+}
@@ -0,0 +1,728 @@
+use actix_rt::task::JoinHandle;
+use chrono::{Local, TimeZone, Utc};
+use futures::FutureExt;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use sbb_api::connections::{get_connections, load_page};
+use sbb_api::models::location::{SearchDateTimeType, StationRef};
+use sbb_api::models::place::Place;
+use sbb_api::models::trip::{PageDirection, StopTime, Trip, TripLeg, TripSearchResponse};
+use sbb_api::places::get_places;
+use sbb_api::Language;
+use simple_error::SimpleError;
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::time::timeout;
+
+use crate::{format_transfers, request_timeout};
+
+// Wait for a pause in typing before asking for suggestions
+const SUGGEST_DELAY: Duration = Duration::from_millis(300);
+const MIN_SUGGEST_CHARS: usize = 2;
+const MAX_SUGGESTIONS: usize = 8;
+// How long to wait for input before checking for suggestions and refreshes again
+const INPUT_POLL: Duration = Duration::from_millis(50);
+// Refresh interval when the API does not suggest one
+const DEFAULT_REFRESH_SECS: u64 = 60;
+
+#[derive(Default)]
+struct Input {
+    text: String,
+    /// Set once a suggestion was picked, so the search uses its UIC reference
+    station: Option<StationRef>,
+}
+
+impl Input {
+    /// A prefilled station; names stay editable text, references are kept for the search
+    fn new(station: Option<StationRef>) -> Self {
+        match station {
+            Some(s) if s.reference.is_some() => Input { text: s.to_string(), station: Some(s) },
+            Some(s) => Input { text: s.name, station: None },
+            None => Input::default(),
+        }
+    }
+
+    fn station(&self) -> Option<StationRef> {
+        self.station.clone().or_else(|| {
+            let text = self.text.trim();
+            text.parse().ok().filter(|_| !text.is_empty())
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Focus {
+    From,
+    To,
+    Results,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Action {
+    None,
+    Search,
+    Earlier,
+    Later,
+    Refresh,
+}
+
+struct Search {
+    from: StationRef,
+    to: StationRef,
+    on: chrono::DateTime<Utc>,
+}
+
+struct App {
+    from: Input,
+    to: Input,
+    focus: Focus,
+    suggestions: Vec<Place>,
+    suggestion: usize,
+    /// Query the current suggestions belong to
+    suggested_for: String,
+    edited_at: Option<Instant>,
+    search: Option<Search>,
+    /// The shown trips with their paging cursors
+    results: TripSearchResponse,
+    selected: usize,
+    expanded: HashSet<String>,
+    refresh: Option<Duration>,
+    /// Language of the API's texts; German when `None`
+    language: Option<Language>,
+    refreshed_at: Instant,
+    status: String,
+    quit: bool,
+}
+
+impl App {
+    fn new(from: Option<StationRef>, to: Option<StationRef>, refresh: Option<Duration>) -> Self {
+        let focus = match (&from, &to) {
+            (Some(_), Some(_)) => Focus::Results,
+            (Some(_), None) => Focus::To,
+            _ => Focus::From,
+        };
+        App {
+            from: Input::new(from),
+            to: Input::new(to),
+            focus,
+            suggestions: Vec::new(),
+            suggestion: 0,
+            suggested_for: String::new(),
+            edited_at: None,
+            search: None,
+            results: TripSearchResponse::default(),
+            selected: 0,
+            expanded: HashSet::new(),
+            refresh,
+            language: None,
+            refreshed_at: Instant::now(),
+            status: "Type origin and destination, Enter to search".to_string(),
+            quit: false,
+        }
+    }
+
+    fn trips(&self) -> &[Trip] {
+        &self.results.trips
+    }
+
+    fn input_mut(&mut self) -> Option<&mut Input> {
+        match self.focus {
+            Focus::From => Some(&mut self.from),
+            Focus::To => Some(&mut self.to),
+            Focus::Results => None,
+        }
+    }
+
+    fn set_focus(&mut self, focus: Focus) {
+        self.focus = focus;
+        self.suggestions.clear();
+        self.suggested_for.clear();
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.kind != KeyEventKind::Press {
+            return Action::None;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return Action::None;
+        }
+
+        match key.code {
+            KeyCode::Tab => {
+                let next = match self.focus {
+                    Focus::From => Focus::To,
+                    Focus::To if !self.trips().is_empty() => Focus::Results,
+                    _ => Focus::From,
+                };
+                self.set_focus(next);
+                Action::None
+            }
+            KeyCode::BackTab => {
+                let prev = match self.focus {
+                    Focus::From if !self.trips().is_empty() => Focus::Results,
+                    Focus::From | Focus::Results => Focus::To,
+                    Focus::To => Focus::From,
+                };
+                self.set_focus(prev);
+                Action::None
+            }
+            _ if self.focus == Focus::Results => self.handle_results_key(key.code),
+            _ => self.handle_input_key(key.code),
+        }
+    }
+
+    fn handle_input_key(&mut self, code: KeyCode) -> Action {
+        match code {
+            KeyCode::Esc if !self.suggestions.is_empty() => self.suggestions.clear(),
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Up => self.suggestion = self.suggestion.saturating_sub(1),
+            KeyCode::Down if self.suggestion + 1 < self.suggestions.len() => self.suggestion += 1,
+            KeyCode::Enter => {
+                if let Some(place) = self.suggestions.get(self.suggestion).cloned() {
+                    let input = self.input_mut().expect("an input has focus");
+                    input.text = place.display_name.clone();
+                    input.station = Some(StationRef::new(&place.display_name, place.identifier.as_deref()));
+                }
+                if self.focus == Focus::From {
+                    self.set_focus(Focus::To);
+                } else if self.from.station().is_some() && self.to.station().is_some() {
+                    self.set_focus(Focus::Results);
+                    return Action::Search;
+                } else {
+                    self.set_focus(Focus::From);
+                }
+            }
+            KeyCode::Backspace | KeyCode::Char(_) => {
+                let input = self.input_mut().expect("an input has focus");
+                match code {
+                    KeyCode::Char(c) => input.text.push(c),
+                    _ => {
+                        input.text.pop();
+                    }
+                }
+                input.station = None;
+                self.edited_at = Some(Instant::now());
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn handle_results_key(&mut self, code: KeyCode) -> Action {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.trips().len() => self.selected += 1,
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if let Some(trip) = self.trips().get(self.selected) {
+                    let id = trip.meta.id.clone();
+                    if !self.expanded.remove(&id) {
+                        self.expanded.insert(id);
+                    }
+                }
+            }
+            KeyCode::PageUp | KeyCode::Char('[') => return Action::Earlier,
+            KeyCode::PageDown | KeyCode::Char(']') => return Action::Later,
+            KeyCode::Char('r') => return Action::Refresh,
+            KeyCode::Char('/') => self.set_focus(Focus::From),
+            _ => {}
+        }
+        Action::None
+    }
+
+    /// The query to fetch suggestions for, once typing has paused.
+    fn pending_suggestion_query(&self) -> Option<String> {
+        let edited_at = self.edited_at?;
+        let input = match self.focus {
+            Focus::From => &self.from,
+            Focus::To => &self.to,
+            Focus::Results => return None,
+        };
+        let query = input.text.trim();
+        let ready = edited_at.elapsed() >= SUGGEST_DELAY
+            && input.station.is_none()
+            && query.chars().count() >= MIN_SUGGEST_CHARS
+            && query != self.suggested_for;
+        ready.then(|| query.to_string())
+    }
+
+    fn refresh_interval(&self) -> Duration {
+        self.refresh.unwrap_or_else(|| {
+            let secs = self.trips().first().and_then(|t| t.meta.next_refresh).filter(|s| *s > 0);
+            Duration::from_secs(secs.map(|s| s as u64).unwrap_or(DEFAULT_REFRESH_SECS))
+        })
+    }
+
+    fn refresh_due(&self) -> bool {
+        self.search.is_some() && self.refreshed_at.elapsed() >= self.refresh_interval()
+    }
+
+    /// Replace the results with a new search, keeping the selection on the same trip when possible.
+    fn set_results(&mut self, resp: TripSearchResponse) {
+        let selected_id = self.trips().get(self.selected).map(|t| t.meta.id.clone());
+        self.results = resp;
+        self.selected = selected_id
+            .and_then(|id| self.trips().iter().position(|t| t.meta.id == id))
+            .unwrap_or(0);
+    }
+
+    /// Keep the selection on the same trip after `added` trips were paged in `direction`.
+    fn paged(&mut self, direction: PageDirection, added: usize) {
+        if direction == PageDirection::Earlier {
+            self.selected += added;
+        }
+    }
+
+    /// Show the places found for `query`, unless the input moved on since they were asked for.
+    fn show_suggestions(&mut self, query: &str, result: Result<Vec<Place>, SimpleError>) {
+        if query != self.suggested_for {
+            return;
+        }
+        match result {
+            Ok(places) => {
+                self.suggestions = places.into_iter().take(MAX_SUGGESTIONS).collect();
+                self.suggestion = 0;
+            }
+            Err(e) => self.status = format!("Suggestions failed: {}", e),
+        }
+    }
+}
+
+/// `sbb tui`: interactive connection search with live suggestions and automatic refresh
+pub async fn run(
+    from: Option<StationRef>,
+    to: Option<StationRef>,
+    refresh: Option<Duration>,
+    language: Option<Language>,
+) -> std::io::Result<()> {
+    let mut app = App { language, ..App::new(from, to, refresh) };
+    let mut terminal = ratatui::init();
+    let initial = if app.focus == Focus::Results { Action::Search } else { Action::None };
+    let result = event_loop(&mut terminal, &mut app, initial).await;
+    ratatui::restore();
+    result
+}
+
+/// Places found for a query, as returned by the background lookup
+type Suggestions = (String, Result<Vec<Place>, SimpleError>);
+
+async fn event_loop(terminal: &mut DefaultTerminal, app: &mut App, initial: Action) -> std::io::Result<()> {
+    let mut action = initial;
+    // Suggestions are looked up in the background so typing is never blocked on the API
+    let mut lookup: Option<JoinHandle<Suggestions>> = None;
+    while !app.quit {
+        if action == Action::None && app.refresh_due() {
+            action = Action::Refresh;
+        }
+        if action != Action::None {
+            app.status = match action {
+                Action::Earlier => "Loading earlier connections…",
+                Action::Later => "Loading later connections…",
+                Action::Refresh => "Refreshing…",
+                _ => "Searching…",
+            }
+            .to_string();
+            terminal.draw(|f| draw(f, app))?;
+            perform(app, action).await;
+            action = Action::None;
+        }
+
+        if let Some(query) = app.pending_suggestion_query() {
+            app.suggested_for = query.clone();
+            if let Some(previous) = lookup.take() {
+                previous.abort();
+            }
+            let language = app.language;
+            lookup = Some(actix_rt::spawn(async move {
+                let result = with_timeout(get_places(&query, language)).await;
+                (query, result)
+            }));
+        }
+        if let Some(finished) = lookup.as_mut().and_then(|handle| handle.now_or_never()) {
+            // A finished handle must not be polled again, whatever the outcome
+            lookup = None;
+            match finished {
+                Ok((query, result)) => app.show_suggestions(&query, result),
+                Err(e) => app.status = format!("Suggestions failed: {}", e),
+            }
+        }
+
+        terminal.draw(|f| draw(f, app))?;
+
+        // Poll without blocking, so the lookup can make progress on this thread while idle
+        if event::poll(Duration::ZERO)? {
+            if let Event::Key(key) = event::read()? {
+                action = app.handle_key(key);
+            }
+        } else {
+            tokio::time::sleep(INPUT_POLL).await;
+        }
+    }
+    Ok(())
+}
+
+async fn with_timeout<T>(request: impl Future<Output = Result<T, SimpleError>>) -> Result<T, SimpleError> {
+    match timeout(request_timeout(), request).await {
+        Ok(result) => result,
+        Err(_) => Err(SimpleError::new("request timed out")),
+    }
+}
+
+/// Searches take the local wall-clock time, like the CLI does
+fn now() -> chrono::DateTime<Utc> {
+    Utc.from_utc_datetime(&Local::now().naive_local())
+}
+
+async fn perform(app: &mut App, action: Action) {
+    if action == Action::Search {
+        let (from, to) = match (app.from.station(), app.to.station()) {
+            (Some(from), Some(to)) => (from, to),
+            _ => {
+                app.status = "Enter both origin and destination".to_string();
+                return;
+            }
+        };
+        app.search = Some(Search { from, to, on: now() });
+        app.results = TripSearchResponse::default();
+        app.expanded.clear();
+        app.selected = 0;
+    }
+    // Refreshing searches again from now on, so connections that have left drop out
+    if let (Action::Refresh, Some(search)) = (action, app.search.as_mut()) {
+        search.on = now();
+    }
+
+    let search = match &app.search {
+        Some(s) => s,
+        None => return,
+    };
+    let direction = match action {
+        Action::Earlier => Some(PageDirection::Earlier),
+        Action::Later => Some(PageDirection::Later),
+        _ => None,
+    };
+
+    let result = match direction {
+        Some(direction) => {
+            if app.results.paging_cursor(direction).is_none() {
+                app.status = "No more connections in this direction".to_string();
+                return;
+            }
+            let page = load_page(
+                &mut app.results,
+                &search.from,
+                &search.to,
+                &search.on,
+                SearchDateTimeType::Departure,
+                direction,
+                app.language,
+            );
+            with_timeout(page).await.map(|added| app.paged(direction, added))
+        }
+        None => {
+            let resp = with_timeout(get_connections(
+                &search.from.name,
+                search.from.reference.as_deref(),
+                &search.to.name,
+                search.to.reference.as_deref(),
+                &search.on,
+                SearchDateTimeType::Departure,
+                app.language,
+            ))
+            .await;
+            resp.map(|resp| app.set_results(resp))
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            app.refreshed_at = Instant::now();
+            app.status = format!(
+                "{} connection(s), updated {}",
+                app.trips().len(),
+                Local::now().format("%H:%M:%S")
+            );
+        }
+        Err(e) => {
+            // Don't retry a failed refresh on every tick
+            app.refreshed_at = Instant::now();
+            app.status = format!("Error: {}", e);
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let [inputs, results, status] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [from_area, to_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(inputs);
+
+    draw_input(frame, from_area, "From", &app.from, app.focus == Focus::From);
+    draw_input(frame, to_area, "To", &app.to, app.focus == Focus::To);
+    draw_results(frame, results, app);
+
+    let help = match app.focus {
+        Focus::Results => "↑↓ select  Enter expand  [ ] earlier/later  r refresh  / new search  q quit",
+        _ => "Tab switch  ↑↓ pick suggestion  Enter accept  Esc quit",
+    };
+    let status_line = Line::from(vec![
+        Span::styled(format!(" {} ", app.status), Style::new().bold()),
+        Span::styled(help, Style::new().fg(Color::DarkGray)),
+    ]);
+    frame.render_widget(Paragraph::new(status_line), status);
+
+    // Suggestions float over the results, below the focused input
+    let anchor = match app.focus {
+        Focus::From => Some(from_area),
+        Focus::To => Some(to_area),
+        Focus::Results => None,
+    };
+    if let Some(anchor) = anchor.filter(|_| !app.suggestions.is_empty()) {
+        let height = (app.suggestions.len() as u16 + 2).min(results.height);
+        let area = Rect { y: anchor.y + anchor.height, height, ..anchor };
+        let items: Vec<ListItem> = app
+            .suggestions
+            .iter()
+            .map(|p| {
+                let reference = p.identifier.as_deref().map(|r| format!("  {}", r)).unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::raw(p.display_name.clone()),
+                    Span::styled(reference, Style::new().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(app.suggestion));
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::bordered())
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+            area,
+            &mut state,
+        );
+    }
+}
+
+fn draw_input(frame: &mut Frame, area: Rect, title: &str, input: &Input, focused: bool) {
+    let style = if focused { Style::new().fg(Color::Cyan) } else { Style::new() };
+    let mut title = title.to_string();
+    if let Some(r) = input.station.as_ref().and_then(|s| s.reference.as_deref()) {
+        title = format!("{} ({})", title, r);
+    }
+    frame.render_widget(
+        Paragraph::new(input.text.as_str()).block(Block::bordered().title(title).border_style(style)),
+        area,
+    );
+    if focused {
+        let x = area.x + 1 + input.text.chars().count() as u16;
+        frame.set_cursor_position((x.min(area.right().saturating_sub(2)), area.y + 1));
+    }
+}
+
+fn draw_results(frame: &mut Frame, area: Rect, app: &App) {
+    let block = Block::bordered().title("Connections").border_style(if app.focus == Focus::Results {
+        Style::new().fg(Color::Cyan)
+    } else {
+        Style::new()
+    });
+    let items: Vec<ListItem> = app
+        .trips()
+        .iter()
+        .map(|t| ListItem::new(trip_text(t, app.expanded.contains(&t.meta.id))))
+        .collect();
+    let mut state = ListState::default().with_selected((!app.trips().is_empty()).then_some(app.selected));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::BOLD).bg(Color::DarkGray)),
+        area,
+        &mut state,
+    );
+}
+
+fn time_span(time: Option<&StopTime>) -> Vec<Span<'static>> {
+    let hh_mm = |t: chrono::DateTime<chrono::FixedOffset>| t.format("%H:%M").to_string();
+    let mut spans = vec![Span::raw(time.and_then(StopTime::time).map(hh_mm).unwrap_or_else(|| "--:--".into()))];
+    if let Some(d) = time.and_then(StopTime::delay_minutes).filter(|d| *d != 0) {
+        spans.push(Span::styled(format!(" {:+}", d), Style::new().fg(Color::Red)));
+    }
+    spans
+}
+
+fn trip_text(trip: &Trip, expanded: bool) -> Text<'static> {
+    let summary = &trip.summary;
+    let dep = &summary.departure_anchor;
+    let arr = &summary.arrival_anchor;
+    let stop_time = |aimed: &Option<String>, expected: &Option<String>| StopTime {
+        time_aimed: aimed.clone(),
+        time_expected: expected.clone(),
+        display_time: None,
+    };
+
+    let mut head = vec![Span::raw(if expanded { "▾ " } else { "▸ " })];
+    head.extend(time_span(Some(&stop_time(&dep.time_aimed, &dep.time_expected))));
+    head.push(Span::raw(" → "));
+    head.extend(time_span(Some(&stop_time(&arr.time_aimed, &arr.time_expected))));
+    head.push(Span::styled(
+        format!("  {:>4} min", trip.duration_minutes().map(|d| d.to_string()).unwrap_or_default()),
+        Style::new().fg(Color::Yellow),
+    ));
+    head.push(Span::styled(format!("  {:<11}", format_transfers(trip.transfers())), Style::new().fg(Color::DarkGray)));
+    if let Some(t) = &dep.transport_designation {
+        head.push(Span::styled(t.to_string(), Style::new().fg(Color::Cyan).bold()));
+    }
+    if let Some(q) = dep.quay.as_ref().filter(|q| !q.name.is_empty()) {
+        let style = if q.changed { Style::new().fg(Color::Red).bold() } else { Style::new().fg(Color::Yellow) };
+        head.push(Span::styled(format!("  Pl. {}{}", q.name, if q.changed { "!" } else { "" }), style));
+    }
+    if trip.is_cancelled() {
+        head.push(Span::styled("  CANCELLED", Style::new().fg(Color::White).bg(Color::Red).bold()));
+    }
+
+    let mut lines = vec![Line::from(head)];
+    if !expanded {
+        return Text::from(lines);
+    }
+
+    let legs = trip.detail.as_ref().map(|d| d.legs.as_slice()).unwrap_or_default();
+    for (idx, leg) in legs.iter().enumerate() {
+        match leg {
+            TripLeg::PtRideLeg(pt) => {
+                let (from, to) = (&pt.departure_stop_point, &pt.arrival_stop_point);
+                let line = pt.first_transport_designation.as_ref().map(|t| t.to_string()).unwrap_or_default();
+                let direction = pt.direction.as_deref().map(|d| format!(" → {}", d)).unwrap_or_default();
+                lines.push(Line::from(vec![
+                    Span::styled(format!("    {}", line), Style::new().fg(Color::Cyan).bold()),
+                    Span::styled(direction, Style::new().fg(Color::DarkGray)),
+                ]));
+                for (stop, time) in [(from, from.departure_time.as_ref()), (to, to.arrival_time.as_ref())] {
+                    let mut spans = vec![Span::raw("      ")];
+                    spans.extend(time_span(time));
+                    spans.push(Span::raw(format!("  {}", stop.display_name)));
+                    if let Some(q) = stop.quay.as_ref().filter(|q| !q.name.is_empty()) {
+                        spans.push(Span::styled(format!("  Pl. {}", q.name), Style::new().fg(Color::Yellow)));
+                    }
+                    lines.push(Line::from(spans));
+
+                    // The API only names the first and last stop of a ride; count the ones in between
+                    let between = trip.leg_stops(idx).len().saturating_sub(2);
+                    if std::ptr::eq(stop, from) && between > 0 {
+                        let label = if between == 1 { "stop" } else { "stops" };
+                        lines.push(Line::styled(
+                            format!("        · {} {} in between", between, label),
+                            Style::new().fg(Color::DarkGray),
+                        ));
+                    }
+                }
+            }
+            TripLeg::AccessLeg(_) => {
+                if let (Some(from), Some(to)) = leg.stop_points() {
+                    lines.push(Line::styled(
+                        format!("    Walk {} → {}", from.display_name, to.display_name),
+                        Style::new().fg(Color::DarkGray).italic(),
+                    ));
+                }
+            }
+            TripLeg::ChangeLeg(_) => {}
+        }
+    }
+    for d in trip.disruptions().iter().filter(|d| !d.rt_type.is_cancellation()) {
+        let text = d.summary.clone().unwrap_or_else(|| d.rt_type.to_string());
+        lines.push(Line::styled(format!("    ⚠ {}", text), Style::new().fg(Color::Red)));
+    }
+    Text::from(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sbb_api::models::place::Coordinates;
+
+    fn response() -> TripSearchResponse {
+        crate::fixtures::response("sbb_api_response_0.json")
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_input_flow() {
+        let mut app = App::new(None, None, None);
+        for c in "Basel".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.from.text, "Basel");
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), Action::None);
+        assert_eq!(app.focus, Focus::To);
+
+        app.to.text = "8507000".to_string();
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), Action::Search);
+        assert_eq!(app.to.station().unwrap().reference.as_deref(), Some("8507000"));
+    }
+
+    #[test]
+    fn test_stale_suggestions_are_ignored() {
+        let mut app = App::new(None, None, None);
+        let place = || Place {
+            display_name: "Basel SBB".to_string(),
+            identifier: Some("8500010".to_string()),
+            place_type: "STOP_PLACE".to_string(),
+            coordinates: Coordinates { latitude: 47.547, longitude: 7.589 },
+        };
+        app.suggested_for = "Base".to_string();
+        app.show_suggestions("Bas", Ok(vec![place()]));
+        assert!(app.suggestions.is_empty());
+
+        app.show_suggestions("Base", Ok(vec![place()]));
+        assert_eq!(app.suggestions.len(), 1);
+    }
+
+    #[test]
+    fn test_paging_keeps_selection() {
+        let mut app = App::new(Some(StationRef::new("Basel SBB", None)), Some(StationRef::new("Bern", None)), None);
+        let mut resp = response();
+        let earlier: Vec<Trip> = resp.trips.drain(..2).collect();
+        app.set_results(resp);
+        app.selected = 1;
+        let selected_id = app.trips()[1].meta.id.clone();
+
+        // An earlier page overlapping the shown trips only adds the new ones
+        let mut page = response();
+        page.trips = earlier.into_iter().chain(app.trips().first().cloned()).collect();
+        let added = app.results.merge_page(page, PageDirection::Earlier);
+        app.paged(PageDirection::Earlier, added);
+        assert_eq!(app.trips().len(), response().trips.len());
+        assert_eq!(app.trips()[app.selected].meta.id, selected_id);
+
+        assert_eq!(app.handle_key(key(KeyCode::Char(']'))), Action::Later);
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.expanded.contains(&selected_id));
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut app = App::new(Some(StationRef::new("Basel SBB", None)), Some(StationRef::new("Bern", None)), None);
+        app.set_results(response());
+        app.expanded.insert(app.trips()[0].meta.id.clone());
+
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|f| draw(f, &app)).unwrap();
+        let screen: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(screen.contains("Basel SBB"));
+        assert!(screen.contains("13:00 → 14:24"));
+        assert!(screen.contains("Olten"));
+        assert!(screen.contains("· 9 stops in between"));
+        assert!(!screen.contains("8500020"));
+    }
+}
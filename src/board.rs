@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use simple_error::SimpleError;
+
+use crate::{get_json, Language};
+use crate::models::board::StationBoardResponse;
+use crate::models::location::{SearchDateTimeType, StationRef};
+
+/// Fetch the departure board of a station from `on` onwards, or its arrival board with
+/// [`SearchDateTimeType::Arrival`].
+///
+/// Like trip searches, `on` carries the local wall-clock time.
+pub async fn get_station_board(
+    station: &StationRef,
+    on: &DateTime<Utc>,
+    dt_type: SearchDateTimeType,
+    language: Option<Language>,
+) -> Result<StationBoardResponse, SimpleError> {
+    let date = on.format("%Y-%m-%d").to_string();
+    let time = on.format("%H:%M").to_string();
+    let dt_str = dt_type.to_string();
+
+    let mut params: Vec<(&str, &str)> = vec![("date", &date), ("time", &time), ("type", &dt_str)];
+    if !station.name.is_empty() {
+        params.push(("placeName", &station.name));
+    }
+    if let Some(r) = &station.reference {
+        params.push(("placeReference", r));
+    }
+
+    get_json("/api/timetable/v2/stationboard", &params, language).await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::get_station_board;
+    use crate::models::location::{SearchDateTimeType, StationRef};
+
+    #[actix_rt::test]
+    #[ignore = "requires live API access"]
+    pub async fn test_get_station_board() {
+        let station = StationRef::new("Olten", Some("8500218"));
+        let result = get_station_board(&station, &chrono::Utc::now(), SearchDateTimeType::Departure, None).await;
+        assert!(result.is_ok(), "error: {:?}", result.err());
+        for entry in result.unwrap().entries {
+            println!("{:?} → {:?}", entry.transport_designation.map(|t| t.to_string()), entry.direction);
+        }
+    }
+}
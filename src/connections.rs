@@ -1,15 +1,18 @@
-use chrono::Utc;
-use simple_error::{bail, SimpleError};
-use reqwest::Url;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use simple_error::SimpleError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use crate::make_request;
-use crate::models::location::SearchDateTimeType;
-use crate::models::trip::TripSearchResponse;
+use crate::{get_json, Language};
+use crate::models::location::{SearchDateTimeType, StationRef};
+use crate::models::trip::{PageDirection, Trip, TripSearchResponse};
 
 /// Fetch connections between two places by name.
 ///
 /// `from_ref` / `to_ref` are optional UIC station IDs (e.g. `"8503000"` for Zürich HB).
 /// Providing them yields more reliable results; omit when only the name is known.
+/// Texts such as notices come in `language`, or German without one.
 pub async fn get_connections(
     from: &str,
     from_ref: Option<&str>,
@@ -17,6 +20,103 @@ pub async fn get_connections(
     to_ref: Option<&str>,
     on: &chrono::DateTime<Utc>,
     dt_type: SearchDateTimeType,
+    language: Option<Language>,
+) -> Result<TripSearchResponse, SimpleError> {
+    search(from, from_ref, to, to_ref, on, dt_type, None, language).await
+}
+
+/// Fetch the connections before or after a previous search, given its
+/// `earlier_paging_cursor` or `later_paging_cursor`.
+///
+/// The cursor only makes sense together with the parameters of the search it came from.
+pub async fn get_connections_page(
+    from: &StationRef,
+    to: &StationRef,
+    on: &chrono::DateTime<Utc>,
+    dt_type: SearchDateTimeType,
+    cursor: &str,
+    language: Option<Language>,
+) -> Result<TripSearchResponse, SimpleError> {
+    search(
+        &from.name,
+        from.reference.as_deref(),
+        &to.name,
+        to.reference.as_deref(),
+        on,
+        dt_type,
+        Some(cursor),
+        language,
+    )
+    .await
+}
+
+/// Load the next page of `resp` in `direction` and merge it in, skipping trips already present.
+///
+/// Returns the number of trips added: 0 when there is no page in that direction, or when it
+/// only repeated known trips. The search parameters must be those `resp` was searched with.
+pub async fn load_page(
+    resp: &mut TripSearchResponse,
+    from: &StationRef,
+    to: &StationRef,
+    on: &chrono::DateTime<Utc>,
+    dt_type: SearchDateTimeType,
+    direction: PageDirection,
+    language: Option<Language>,
+) -> Result<usize, SimpleError> {
+    let cursor = match resp.paging_cursor(direction) {
+        Some(c) => c.to_string(),
+        None => return Ok(0),
+    };
+    let page = get_connections_page(from, to, on, dt_type, &cursor, language).await?;
+    Ok(resp.merge_page(page, direction))
+}
+
+/// Pages searched beyond the first one before [`find_connection`] gives up.
+const MAX_EXTRA_PAGES: usize = 3;
+
+/// Search at `on` (local wall-clock time), paging in `direction` until `pick` finds a connection.
+pub(crate) async fn find_connection(
+    from: &StationRef,
+    to: &StationRef,
+    on: NaiveDateTime,
+    dt_type: SearchDateTimeType,
+    direction: PageDirection,
+    language: Option<Language>,
+    pick: impl Fn(&[Trip]) -> Option<&Trip>,
+) -> Result<Option<Trip>, SimpleError> {
+    let on = Utc.from_utc_datetime(&on);
+    let mut resp = get_connections(
+        &from.name,
+        from.reference.as_deref(),
+        &to.name,
+        to.reference.as_deref(),
+        &on,
+        dt_type,
+        language,
+    )
+    .await?;
+
+    for _ in 0..MAX_EXTRA_PAGES {
+        if pick(&resp.trips).is_some() {
+            break;
+        }
+        if load_page(&mut resp, from, to, &on, dt_type, direction, language).await? == 0 {
+            break;
+        }
+    }
+    Ok(pick(&resp.trips).cloned())
+}
+
+#[allow(clippy::too_many_arguments)] // the query parameters of the endpoint
+async fn search(
+    from: &str,
+    from_ref: Option<&str>,
+    to: &str,
+    to_ref: Option<&str>,
+    on: &chrono::DateTime<Utc>,
+    dt_type: SearchDateTimeType,
+    cursor: Option<&str>,
+    language: Option<Language>,
 ) -> Result<TripSearchResponse, SimpleError> {
     let date = on.format("%Y-%m-%d").to_string();
     let time = on.format("%H:%M").to_string();
@@ -38,38 +138,140 @@ pub async fn get_connections(
     if let Some(r) = to_ref {
         params.push(("arrivalReference", r));
     }
+    if let Some(c) = cursor {
+        params.push(("pagingCursor", c));
+    }
 
-    let base_path = format!("/api/timetable/v2/trips");
-    let url = Url::parse_with_params(
-        &format!("{}{}", crate::API_ENDPOINT, base_path),
-        &params,
-    )
-    .map_err(|e| SimpleError::new(format!("URL parse error: {}", e)))?;
+    get_json("/api/timetable/v2/trips", &params, language).await
+}
+
+type SearchKey = (StationRef, StationRef, String, SearchDateTimeType, Option<Language>);
+
+/// A search that is running or done, shared by everyone asking for it.
+type SharedSearch = Shared<BoxFuture<'static, Result<Arc<TripSearchResponse>, String>>>;
+
+/// Memoizes trip searches, so repeated queries for the same route and minute hit the API once.
+///
+/// Used by the batch helpers (reachability, matrices, ...) that may ask for the same
+/// search many times. Concurrent identical searches wait for the same request. Entries
+/// never expire, failed searches are forgotten; create a new cache for fresh real-time data.
+#[derive(Default)]
+pub struct ConnectionCache {
+    entries: Mutex<HashMap<SearchKey, SharedSearch>>,
+}
+
+impl ConnectionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(
+        from: &StationRef,
+        to: &StationRef,
+        on: &chrono::DateTime<Utc>,
+        dt_type: SearchDateTimeType,
+        language: Option<Language>,
+    ) -> SearchKey {
+        (from.clone(), to.clone(), on.format("%Y-%m-%dT%H:%M").to_string(), dt_type, language)
+    }
+
+    /// Same as [`get_connections`], answered from the cache when possible.
+    pub async fn get_connections(
+        &self,
+        from: &StationRef,
+        to: &StationRef,
+        on: &chrono::DateTime<Utc>,
+        dt_type: SearchDateTimeType,
+        language: Option<Language>,
+    ) -> Result<Arc<TripSearchResponse>, SimpleError> {
+        let key = Self::key(from, to, on, dt_type, language);
+        let search = {
+            let mut entries = self.entries.lock().expect("cache lock poisoned");
+            entries
+                .entry(key.clone())
+                .or_insert_with(|| {
+                    let (from, to, on) = (from.clone(), to.clone(), *on);
+                    async move {
+                        get_connections(
+                            &from.name,
+                            from.reference.as_deref(),
+                            &to.name,
+                            to.reference.as_deref(),
+                            &on,
+                            dt_type,
+                            language,
+                        )
+                        .await
+                            .map(Arc::new)
+                            .map_err(|e| e.to_string())
+                    }
+                    .boxed()
+                    .shared()
+                })
+                .clone()
+        };
 
-    let resp = make_request(url, &base_path)
-        .await
-        .map_err(|e| SimpleError::new(format!("Request error: {}", e)))?;
+        let result = search.await;
+        if result.is_err() {
+            self.entries.lock().expect("cache lock poisoned").remove(&key);
+        }
+        result.map_err(SimpleError::new)
+    }
 
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
-        bail!("HTTP {}: {}", status, body)
+    /// Answer a search with `resp` without asking the API.
+    #[cfg(test)]
+    pub(crate) fn insert(
+        &self,
+        from: &StationRef,
+        to: &StationRef,
+        on: &chrono::DateTime<Utc>,
+        dt_type: SearchDateTimeType,
+        resp: TripSearchResponse,
+    ) {
+        let ready = futures::future::ready(Ok(Arc::new(resp))).boxed().shared();
+        self.entries
+            .lock()
+            .expect("cache lock poisoned")
+            .insert(Self::key(from, to, on, dt_type, None), ready);
     }
 
-    let text = resp
-        .text()
-        .await
-        .map_err(|e| SimpleError::new(format!("Response read error: {}", e)))?;
+    /// Number of cached searches.
+    pub fn len(&self) -> usize {
+        self.entries.lock().expect("cache lock poisoned").len()
+    }
 
-    serde_json::from_str(&text)
-        .map_err(|e| SimpleError::new(format!("JSON parse error: {}", e)))
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use chrono::{Datelike, TimeZone, Utc};
-    use crate::connections::get_connections;
-    use crate::models::location::SearchDateTimeType;
+    use std::sync::Arc;
+    use crate::connections::{get_connections, load_page, ConnectionCache};
+    use crate::models::location::{SearchDateTimeType, StationRef};
+    use crate::models::trip::fixtures::response;
+    use crate::models::trip::PageDirection;
+
+    #[actix_rt::test]
+    async fn test_cache_answers_identical_searches_once() {
+        let resp = response("sbb_api_response_0.json");
+        let from = StationRef::new("Basel SBB", Some("8500010"));
+        let to = StationRef::new("Bern", Some("8507000"));
+        let on = Utc.with_ymd_and_hms(2026, 2, 22, 13, 0, 0).unwrap();
+
+        let cache = ConnectionCache::new();
+        cache.insert(&from, &to, &on, SearchDateTimeType::Departure, resp);
+        // Seconds don't matter, the API takes minutes
+        let later = on + chrono::Duration::seconds(30);
+        let (a, b) = futures::join!(
+            cache.get_connections(&from, &to, &on, SearchDateTimeType::Departure, None),
+            cache.get_connections(&from, &to, &later, SearchDateTimeType::Departure, None),
+        );
+        assert!(Arc::ptr_eq(&a.unwrap(), &b.unwrap()));
+        assert_eq!(cache.len(), 1);
+    }
 
     #[actix_rt::test]
     #[ignore = "requires live API access"]
@@ -81,6 +283,7 @@ mod tests {
             "Basel SBB", Some("8500010"),
             &date,
             SearchDateTimeType::Departure,
+            None,
         )
         .await;
         assert!(result.is_ok(), "error: {:?}", result.err());
@@ -102,9 +305,28 @@ mod tests {
             "Bern", None,
             &date,
             SearchDateTimeType::Departure,
+            None,
         )
         .await;
         assert!(result.is_ok(), "error: {:?}", result.err());
         println!("Trips: {}", result.unwrap().trips.len());
     }
+
+    #[actix_rt::test]
+    #[ignore = "requires live API access"]
+    pub async fn test_load_later_page() {
+        let today = chrono::offset::Local::now();
+        let date = Utc.with_ymd_and_hms(today.year(), today.month(), today.day(), 12, 0, 0).unwrap();
+        let from = StationRef::new("Zürich HB", Some("8503000"));
+        let to = StationRef::new("Bern", Some("8507000"));
+        let mut resp = get_connections("Zürich HB", Some("8503000"), "Bern", Some("8507000"), &date, SearchDateTimeType::Departure, None)
+            .await
+            .unwrap();
+        let before = resp.trips.len();
+        let added = load_page(&mut resp, &from, &to, &date, SearchDateTimeType::Departure, PageDirection::Later, None)
+            .await
+            .unwrap();
+        assert!(added > 0);
+        assert_eq!(resp.trips.len(), before + added);
+    }
 }
@@ -0,0 +1,227 @@
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use simple_error::{bail, SimpleError};
+
+/// Parse a date and/or time as a person would type it, relative to `now` (local time).
+///
+/// Accepted, in any combination of one date, one time and any number of offsets:
+/// - times: `7:45`, `17:30`
+/// - dates: `2026-03-15`, `15.3.`, `15.3.2026`, `today`, `tomorrow`, `yesterday`,
+///   weekday names (`monday`, `fri`: the next such day, today included; `next monday` excludes today)
+/// - offsets: `now`, `in 20 min`, `in 1 h 30 min`, `+1d`, `-2h`, `+30m`
+///
+/// Whatever is not given is taken from `now`, so `tomorrow` keeps the current time and `7:45` is today.
+/// Offsets apply last: `tomorrow 7:45 +1d` is the day after tomorrow at 7:45.
+/// `15.3.` without a year is the next 15 March, today included.
+pub fn parse_date_time(input: &str, now: NaiveDateTime) -> Result<NaiveDateTime, SimpleError> {
+    let lower = input.to_lowercase();
+    let mut tokens = lower.split_whitespace().peekable();
+    let today = now.date();
+    let mut date: Option<NaiveDate> = None;
+    let mut time: Option<NaiveTime> = None;
+    let mut offset = Duration::zero();
+
+    let mut set_date = |d: NaiveDate| -> Result<(), SimpleError> {
+        if date.replace(d).is_some() {
+            bail!("more than one date in '{}'", input.trim());
+        }
+        Ok(())
+    };
+
+    while let Some(token) = tokens.next() {
+        match token {
+            "now" => {}
+            "at" => {}
+            "today" => set_date(today)?,
+            "tomorrow" => set_date(today + Duration::days(1))?,
+            "yesterday" => set_date(today - Duration::days(1))?,
+            "next" => {
+                let weekday = tokens
+                    .next()
+                    .and_then(|t| t.parse::<Weekday>().ok())
+                    .ok_or_else(|| SimpleError::new(format!("expected a weekday after 'next' in '{}'", input.trim())))?;
+                set_date(next_weekday(today + Duration::days(1), weekday))?;
+            }
+            "in" => {
+                let mut amounts = 0;
+                while let Some(&t) = tokens.peek() {
+                    let (value, unit) = split_amount(t);
+                    let Ok(value) = value.parse::<i64>() else { break };
+                    tokens.next();
+                    let unit = if unit.is_empty() { tokens.next().unwrap_or("") } else { unit };
+                    offset = add_amount(offset, value, unit)?;
+                    amounts += 1;
+                }
+                if amounts == 0 {
+                    bail!("expected an amount after 'in', like 'in 20 min'");
+                }
+            }
+            t if t.starts_with('+') || t.starts_with('-') => {
+                let (value, unit) = split_amount(&t[1..]);
+                let value: i64 = value
+                    .parse()
+                    .map_err(|_| SimpleError::new(format!("invalid offset '{}', expected e.g. +1d or +30m", t)))?;
+                let value = if t.starts_with('-') { -value } else { value };
+                offset = add_amount(offset, value, unit)?;
+            }
+            t if t.contains(':') => {
+                let parsed = NaiveTime::parse_from_str(t, "%H:%M")
+                    .map_err(|_| SimpleError::new(format!("invalid time '{}', expected HH:MM", t)))?;
+                if time.replace(parsed).is_some() {
+                    bail!("more than one time in '{}'", input.trim());
+                }
+            }
+            t if t.contains('.') || t.contains('-') => set_date(parse_date(t, today)?)?,
+            t => match t.parse::<Weekday>() {
+                Ok(weekday) => set_date(next_weekday(today, weekday))?,
+                Err(_) => bail!(
+                    "cannot understand '{}': expected a time (7:45), a date (15.3., tomorrow, friday) or an offset (in 20 min, +1d)",
+                    t
+                ),
+            },
+        }
+    }
+
+    let date = date.unwrap_or(today);
+    let time = time.unwrap_or(now.time());
+    date.and_time(time)
+        .checked_add_signed(offset)
+        .ok_or_else(|| out_of_range(input))
+}
+
+/// `2026-03-15`, `15.3.2026`, `15.03.26` or `15.3.` (the next such day)
+fn parse_date(token: &str, today: NaiveDate) -> Result<NaiveDate, SimpleError> {
+    let invalid = || SimpleError::new(format!("invalid date '{}', expected e.g. 15.3., 15.3.2026 or 2026-03-15", token));
+
+    if token.contains('-') {
+        return NaiveDate::parse_from_str(token, "%Y-%m-%d").map_err(|_| invalid());
+    }
+
+    let parts: Vec<&str> = token.trim_end_matches('.').split('.').collect();
+    let number = |s: &str| s.parse::<u32>().map_err(|_| invalid());
+    match parts.as_slice() {
+        [day, month] => {
+            let (day, month) = (number(day)?, number(month)?);
+            let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+            match this_year {
+                Some(d) if d >= today => Ok(d),
+                // Already past (or 29.2. outside a leap year): the next one
+                _ => (1..=4)
+                    .find_map(|y| NaiveDate::from_ymd_opt(today.year() + y, month, day))
+                    .ok_or_else(invalid),
+            }
+        }
+        [day, month, year] => {
+            let year = match number(year)? {
+                y if year.len() == 2 => 2000 + y as i32,
+                y => y as i32,
+            };
+            NaiveDate::from_ymd_opt(year, number(month)?, number(day)?).ok_or_else(invalid)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// The first `weekday` on or after `from`
+fn next_weekday(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days = (weekday.num_days_from_monday() + 7 - from.weekday().num_days_from_monday()) % 7;
+    from + Duration::days(days as i64)
+}
+
+/// Split "20min" into ("20", "min")
+fn split_amount(token: &str) -> (&str, &str) {
+    let end = token.find(|c: char| !c.is_ascii_digit()).unwrap_or(token.len());
+    token.split_at(end)
+}
+
+/// `offset` plus `value` of `unit`, or an error for unknown units and offsets out of range
+fn add_amount(offset: Duration, value: i64, unit: &str) -> Result<Duration, SimpleError> {
+    let amount = match unit {
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(value),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::try_hours(value),
+        "d" | "day" | "days" => Duration::try_days(value),
+        "w" | "week" | "weeks" => Duration::try_weeks(value),
+        _ => bail!("unknown time unit '{}', expected min, h, d or w", unit),
+    };
+    amount
+        .and_then(|a| offset.checked_add(&a))
+        .ok_or_else(|| SimpleError::new(format!("offset of {} {} is too large", value, unit)))
+}
+
+fn out_of_range(input: &str) -> SimpleError {
+    SimpleError::new(format!("'{}' is too far in the past or future", input.trim()))
+}
+
+/// Format a time as HH:MM, or an empty string when unknown.
+pub fn format_clock_time(time: Option<DateTime<FixedOffset>>) -> String {
+    time.map(|t| t.format("%H:%M").to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Wednesday, 18 March 2026, 10:30
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 3, 18).unwrap().and_hms_opt(10, 30, 0).unwrap()
+    }
+
+    fn parse(input: &str) -> String {
+        parse_date_time(input, now()).unwrap().format("%Y-%m-%d %H:%M").to_string()
+    }
+
+    #[test]
+    fn test_absolute() {
+        assert_eq!(parse(""), "2026-03-18 10:30");
+        assert_eq!(parse("now"), "2026-03-18 10:30");
+        assert_eq!(parse("7:45"), "2026-03-18 07:45");
+        assert_eq!(parse("2026-04-01 17:05"), "2026-04-01 17:05");
+        assert_eq!(parse("15.4."), "2026-04-15 10:30");
+        assert_eq!(parse("18.3."), "2026-03-18 10:30");
+        // Already past this year
+        assert_eq!(parse("15.3. 8:00"), "2027-03-15 08:00");
+        assert_eq!(parse("15.03.2026"), "2026-03-15 10:30");
+        assert_eq!(parse("1.5.27"), "2027-05-01 10:30");
+    }
+
+    #[test]
+    fn test_relative() {
+        assert_eq!(parse("in 20 min"), "2026-03-18 10:50");
+        assert_eq!(parse("in 20min"), "2026-03-18 10:50");
+        assert_eq!(parse("in 1 h 30 min"), "2026-03-18 12:00");
+        assert_eq!(parse("tomorrow 7:45"), "2026-03-19 07:45");
+        assert_eq!(parse("Tomorrow at 7:45"), "2026-03-19 07:45");
+        assert_eq!(parse("yesterday"), "2026-03-17 10:30");
+        assert_eq!(parse("+1d"), "2026-03-19 10:30");
+        assert_eq!(parse("8:00 -1d"), "2026-03-17 08:00");
+        assert_eq!(parse("+90m"), "2026-03-18 12:00");
+        // Offsets may cross midnight
+        assert_eq!(parse("23:50 +20min"), "2026-03-19 00:10");
+    }
+
+    #[test]
+    fn test_weekdays() {
+        assert_eq!(parse("friday"), "2026-03-20 10:30");
+        assert_eq!(parse("mon 6:00"), "2026-03-23 06:00");
+        // Today is a Wednesday
+        assert_eq!(parse("wednesday"), "2026-03-18 10:30");
+        assert_eq!(parse("next wed"), "2026-03-25 10:30");
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |input: &str| parse_date_time(input, now()).unwrap_err().to_string();
+        assert!(err("25:00").contains("invalid time '25:00'"));
+        assert!(err("31.2.").contains("invalid date '31.2.'"));
+        assert!(err("2026-13-01").contains("invalid date"));
+        assert!(err("in").contains("expected an amount"));
+        assert!(err("in 5 parsecs").contains("unknown time unit 'parsecs'"));
+        assert!(err("+xd").contains("invalid offset"));
+        assert!(err("tomorrow friday").contains("more than one date"));
+        assert!(err("7:00 8:00").contains("more than one time"));
+        assert!(err("soon").contains("cannot understand 'soon'"));
+        assert!(err("next week").contains("expected a weekday"));
+        assert!(err("+99999999999d").contains("too far in the past or future"));
+        assert!(err("+9999999999999999w").contains("too large"));
+        assert!(err("in 9223372036854775807 min").contains("too large"));
+    }
+}
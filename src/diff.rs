@@ -0,0 +1,358 @@
+use serde::Serialize;
+use std::fmt;
+
+use crate::models::trip::{Disruption, PtRideLeg, RtType, StopPoint, StopTime, Trip, TripLeg};
+
+/// Which end of a leg a change concerns.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopEvent {
+    Departure,
+    Arrival,
+}
+
+/// A change between two versions of the same trip, as returned by [`diff_trips`].
+///
+/// `leg_index` refers to the legs of the newer trip, except for [`TripChange::LegRemoved`].
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TripChange {
+    /// The delay at a stop changed. Delays are in minutes, `None` when unknown.
+    Delay {
+        leg_index: usize,
+        stop: String,
+        event: StopEvent,
+        old_minutes: Option<i64>,
+        new_minutes: Option<i64>,
+    },
+    /// The platform at a stop changed. `changed` mirrors `Quay::changed` of the new platform,
+    /// i.e. whether it differs from the timetable.
+    Platform {
+        leg_index: usize,
+        stop: String,
+        event: StopEvent,
+        old: Option<String>,
+        new: Option<String>,
+        changed: bool,
+    },
+    /// A leg, or the whole trip when `leg_index` is `None`, has been cancelled.
+    Cancelled { leg_index: Option<usize> },
+    /// A previously cancelled leg or trip runs again.
+    Reinstated { leg_index: Option<usize> },
+    /// A leg is now served by a different line, e.g. a replacement bus.
+    LegReplaced {
+        leg_index: usize,
+        old_line: Option<String>,
+        new_line: Option<String>,
+    },
+    LegAdded { leg_index: usize, line: Option<String> },
+    /// `leg_index` refers to the legs of the older trip.
+    LegRemoved { leg_index: usize, line: Option<String> },
+    /// A new real-time message appeared (cancellations, delays and platform changes
+    /// are reported by their own variants).
+    DisruptionAdded { disruption: Disruption },
+    DisruptionCleared { disruption: Disruption },
+}
+
+impl fmt::Display for TripChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let at = |event: &StopEvent, stop: &str| match event {
+            StopEvent::Departure => format!("departure from {}", stop),
+            StopEvent::Arrival => format!("arrival at {}", stop),
+        };
+        let minutes = |m: &Option<i64>| match m {
+            Some(m) => format!("{:+} min", m),
+            None => "unknown".to_string(),
+        };
+        let text = |t: &Option<String>| t.clone().unwrap_or_else(|| "?".to_string());
+        match self {
+            TripChange::Delay { stop, event, old_minutes, new_minutes, .. } => write!(
+                f,
+                "Delay of {} changed from {} to {}",
+                at(event, stop),
+                minutes(old_minutes),
+                minutes(new_minutes)
+            ),
+            TripChange::Platform { stop, event, old, new, .. } => {
+                write!(f, "Platform for {} changed from {} to {}", at(event, stop), text(old), text(new))
+            }
+            TripChange::Cancelled { leg_index: Some(i) } => write!(f, "Leg {} cancelled", i + 1),
+            TripChange::Cancelled { leg_index: None } => write!(f, "Connection cancelled"),
+            TripChange::Reinstated { leg_index: Some(i) } => write!(f, "Leg {} runs again", i + 1),
+            TripChange::Reinstated { leg_index: None } => write!(f, "Connection runs again"),
+            TripChange::LegReplaced { leg_index, old_line, new_line } => write!(
+                f,
+                "Leg {} now served by {} instead of {}",
+                leg_index + 1,
+                text(new_line),
+                text(old_line)
+            ),
+            TripChange::LegAdded { leg_index, line } => write!(f, "New leg {}: {}", leg_index + 1, text(line)),
+            TripChange::LegRemoved { leg_index, line } => {
+                write!(f, "Leg {} ({}) removed", leg_index + 1, text(line))
+            }
+            TripChange::DisruptionAdded { disruption } => match &disruption.summary {
+                Some(s) => write!(f, "{}: {}", disruption.rt_type, s),
+                None => write!(f, "{}", disruption.rt_type),
+            },
+            TripChange::DisruptionCleared { disruption } => match &disruption.summary {
+                Some(s) => write!(f, "Resolved: {}: {}", disruption.rt_type, s),
+                None => write!(f, "Resolved: {}", disruption.rt_type),
+            },
+        }
+    }
+}
+
+/// Pair up the trips of two searches that share a `TripMeta::id`.
+pub fn match_trips<'a>(old: &'a [Trip], new: &'a [Trip]) -> Vec<(&'a Trip, &'a Trip)> {
+    new.iter()
+        .filter_map(|n| old.iter().find(|o| o.meta.id == n.meta.id).map(|o| (o, n)))
+        .collect()
+}
+
+/// Compute what changed from `old` to `new`, two versions of the same trip.
+///
+/// Legs are matched by their `rokas_leg_identifier`, or by position when the API omits it.
+pub fn diff_trips(old: &Trip, new: &Trip) -> Vec<TripChange> {
+    let old_legs = ride_legs(old);
+    let new_legs = ride_legs(new);
+    let mut changes = Vec::new();
+
+    for (key, idx, leg) in &new_legs {
+        let previous = old_legs.iter().find(|(k, _, _)| k == key);
+        let (_, _, prev) = match previous {
+            Some(p) => p,
+            None => {
+                changes.push(TripChange::LegAdded { leg_index: *idx, line: line(leg) });
+                continue;
+            }
+        };
+
+        if line(prev) != line(leg) {
+            changes.push(TripChange::LegReplaced {
+                leg_index: *idx,
+                old_line: line(prev),
+                new_line: line(leg),
+            });
+        }
+
+        let stops = [
+            (StopEvent::Departure, &prev.departure_stop_point, &leg.departure_stop_point),
+            (StopEvent::Arrival, &prev.arrival_stop_point, &leg.arrival_stop_point),
+        ];
+        for (event, before, after) in stops {
+            let delay = |s: &StopPoint| stop_time(s, event).and_then(StopTime::delay_minutes);
+            if delay(before) != delay(after) {
+                changes.push(TripChange::Delay {
+                    leg_index: *idx,
+                    stop: after.display_name.clone(),
+                    event,
+                    old_minutes: delay(before),
+                    new_minutes: delay(after),
+                });
+            }
+
+            let quay = |s: &StopPoint| s.quay.as_ref().map(|q| q.name.clone());
+            if quay(before) != quay(after) {
+                changes.push(TripChange::Platform {
+                    leg_index: *idx,
+                    stop: after.display_name.clone(),
+                    event,
+                    old: quay(before),
+                    new: quay(after),
+                    changed: after.quay.as_ref().map(|q| q.changed).unwrap_or(false),
+                });
+            }
+        }
+    }
+
+    for (key, idx, leg) in &old_legs {
+        if !new_legs.iter().any(|(k, _, _)| k == key) {
+            changes.push(TripChange::LegRemoved { leg_index: *idx, line: line(leg) });
+        }
+    }
+
+    changes.extend(diff_disruptions(old, new));
+    changes
+}
+
+type LegEvent = (Option<String>, Disruption);
+
+fn diff_disruptions(old: &Trip, new: &Trip) -> Vec<TripChange> {
+    let old_events = keyed_disruptions(old);
+    let new_events = keyed_disruptions(new);
+    // Compare by leg identity, since leg indices may shift between versions
+    let same = |a: &LegEvent, b: &LegEvent| a.0 == b.0 && a.1.rt_type == b.1.rt_type && a.1.summary == b.1.summary;
+    let is_cancelled = |events: &[LegEvent], key: &Option<String>| {
+        events.iter().any(|(k, d)| k == key && d.rt_type.is_cancellation())
+    };
+
+    let mut changes = Vec::new();
+    let mut seen_cancellations: Vec<&Option<String>> = Vec::new();
+    for (key, d) in &new_events {
+        if d.rt_type.is_cancellation() {
+            if !is_cancelled(&old_events, key) && !seen_cancellations.contains(&key) {
+                seen_cancellations.push(key);
+                changes.push(TripChange::Cancelled { leg_index: d.leg_index });
+            }
+        } else if is_reported_separately(&d.rt_type) {
+            continue;
+        } else if !old_events.iter().any(|o| same(o, &(key.clone(), d.clone()))) {
+            changes.push(TripChange::DisruptionAdded { disruption: d.clone() });
+        }
+    }
+
+    for (key, d) in &old_events {
+        if d.rt_type.is_cancellation() {
+            if !is_cancelled(&new_events, key) && !seen_cancellations.contains(&key) {
+                seen_cancellations.push(key);
+                let leg_index = key.as_ref().and_then(|k| leg_index_of(new, k));
+                changes.push(TripChange::Reinstated { leg_index });
+            }
+        } else if !is_reported_separately(&d.rt_type) && !new_events.iter().any(|n| same(n, &(key.clone(), d.clone()))) {
+            changes.push(TripChange::DisruptionCleared { disruption: d.clone() });
+        }
+    }
+    changes
+}
+
+fn is_reported_separately(rt_type: &RtType) -> bool {
+    matches!(rt_type, RtType::Delay | RtType::PlatformChange)
+}
+
+fn keyed_disruptions(trip: &Trip) -> Vec<LegEvent> {
+    let legs = trip.detail.as_ref().map(|d| d.legs.as_slice()).unwrap_or_default();
+    trip.disruptions()
+        .into_iter()
+        .map(|d| {
+            let key = d.leg_index.and_then(|i| legs.get(i)).map(|l| leg_key(l, d.leg_index.unwrap_or(0)));
+            (key, d)
+        })
+        .collect()
+}
+
+fn leg_key(leg: &TripLeg, idx: usize) -> String {
+    leg.rokas_leg_identifier()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("#{}", idx))
+}
+
+fn leg_index_of(trip: &Trip, key: &str) -> Option<usize> {
+    let legs = trip.detail.as_ref()?.legs.iter();
+    legs.enumerate().position(|(i, l)| leg_key(l, i) == key)
+}
+
+fn ride_legs(trip: &Trip) -> Vec<(String, usize, &PtRideLeg)> {
+    let legs = trip.detail.as_ref().map(|d| d.legs.as_slice()).unwrap_or_default();
+    legs.iter()
+        .enumerate()
+        .filter_map(|(i, l)| match l {
+            TripLeg::PtRideLeg(pt) => Some((leg_key(l, i), i, pt)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn line(leg: &PtRideLeg) -> Option<String> {
+    leg.first_transport_designation.as_ref().map(|t| t.to_string())
+}
+
+fn stop_time(stop: &StopPoint, event: StopEvent) -> Option<&StopTime> {
+    match event {
+        StopEvent::Departure => stop.departure_time.as_ref(),
+        StopEvent::Arrival => stop.arrival_time.as_ref(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+    use crate::models::trip::RtActionInfo;
+
+    fn trip() -> Trip {
+        trips("sbb_api_response_0.json").remove(0)
+    }
+
+    fn ride_mut(trip: &mut Trip, idx: usize) -> &mut PtRideLeg {
+        match &mut trip.detail.as_mut().unwrap().legs[idx] {
+            TripLeg::PtRideLeg(pt) => pt,
+            _ => panic!("Expected PtRideLeg"),
+        }
+    }
+
+    #[test]
+    fn test_identical_trips() {
+        assert!(diff_trips(&trip(), &trip()).is_empty());
+    }
+
+    #[test]
+    fn test_delay_and_platform() {
+        let old = trip();
+        let mut new = old.clone();
+        let leg = ride_mut(&mut new, 2);
+        leg.arrival_stop_point.arrival_time.as_mut().unwrap().time_expected =
+            Some("2026-02-22T14:29:00+01:00".to_string());
+        leg.departure_stop_point.quay.as_mut().unwrap().name = "Gl. 1".to_string();
+
+        let changes = diff_trips(&old, &new);
+        assert_eq!(changes.len(), 2, "{:?}", changes);
+        assert!(changes.contains(&TripChange::Delay {
+            leg_index: 2,
+            stop: "Bern".to_string(),
+            event: StopEvent::Arrival,
+            old_minutes: Some(0),
+            new_minutes: Some(5),
+        }));
+        let platform = changes.iter().find(|c| matches!(c, TripChange::Platform { .. })).unwrap();
+        assert!(platform.to_string().starts_with("Platform for departure from Olten changed from"));
+        assert!(platform.to_string().ends_with("to Gl. 1"));
+    }
+
+    #[test]
+    fn test_cancellation_and_replacement() {
+        let old = trip();
+        let mut new = old.clone();
+        ride_mut(&mut new, 0).rt_action_infos.push(RtActionInfo {
+            rt_type: Some(RtType::Cancelled),
+            summary: Some("Fällt aus".to_string()),
+            dialog_title: None,
+        });
+        ride_mut(&mut new, 2).first_transport_designation.as_mut().unwrap().transport_display_name = "EXT".to_string();
+
+        let changes = diff_trips(&old, &new);
+        assert!(changes.contains(&TripChange::Cancelled { leg_index: Some(0) }));
+        assert!(changes.iter().any(|c| matches!(c, TripChange::LegReplaced { leg_index: 2, .. })));
+
+        // And back again
+        let changes = diff_trips(&new, &old);
+        assert!(changes.contains(&TripChange::Reinstated { leg_index: Some(0) }));
+    }
+
+    #[test]
+    fn test_removed_leg_and_disruption() {
+        let old = trip();
+        let mut new = old.clone();
+        new.detail.as_mut().unwrap().legs.truncate(1);
+        ride_mut(&mut new, 0).rt_action_infos.push(RtActionInfo {
+            rt_type: Some(RtType::Disruption),
+            summary: Some("Fahrleitungsstörung".to_string()),
+            dialog_title: None,
+        });
+
+        let changes = diff_trips(&old, &new);
+        assert!(changes.iter().any(|c| matches!(c, TripChange::LegRemoved { leg_index: 2, .. })));
+        assert!(changes.iter().any(|c| matches!(
+            c,
+            TripChange::DisruptionAdded { disruption } if disruption.rt_type == RtType::Disruption
+        )));
+    }
+
+    #[test]
+    fn test_match_trips() {
+        let trips = trips("sbb_api_response_0.json");
+        let pairs = match_trips(&trips[1..], &trips);
+        assert_eq!(pairs.len(), trips.len() - 1);
+        assert!(pairs.iter().all(|(o, n)| o.meta.id == n.meta.id));
+    }
+}
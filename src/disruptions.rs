@@ -0,0 +1,76 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use simple_error::SimpleError;
+
+use crate::{get_json, Language};
+use crate::models::disruption::{DisruptionMessage, DisruptionResponse};
+
+/// Criteria for narrowing down the disruption feed. Empty criteria match every message.
+#[derive(Debug, Clone, Default)]
+pub struct DisruptionFilter {
+    /// Only messages affecting this UIC station reference.
+    pub station: Option<String>,
+    /// Only messages affecting this line (e.g. "IC 5").
+    pub line: Option<String>,
+    /// Only messages valid at this time. [`get_disruptions`] uses the current time when unset.
+    pub valid_at: Option<DateTime<FixedOffset>>,
+}
+
+impl DisruptionFilter {
+    pub fn matches(&self, msg: &DisruptionMessage) -> bool {
+        self.station.as_deref().map(|s| msg.affects_station(s)).unwrap_or(true)
+            && self.line.as_deref().map(|l| msg.affects_line(l)).unwrap_or(true)
+            && self.valid_at.map(|at| msg.is_valid_at(&at)).unwrap_or(true)
+    }
+}
+
+/// Fetch the network-wide disruption messages matching `filter`. Messages outside their
+/// validity periods are left out.
+pub async fn get_disruptions(
+    filter: &DisruptionFilter,
+    language: Option<Language>,
+) -> Result<Vec<DisruptionMessage>, SimpleError> {
+    let filter = DisruptionFilter {
+        valid_at: filter.valid_at.or_else(|| Some(Utc::now().fixed_offset())),
+        ..filter.clone()
+    };
+    let resp: DisruptionResponse = get_json("/api/timetable/v2/disruptions", &[], language).await?;
+    Ok(resp.messages.into_iter().filter(|m| filter.matches(m)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::disruptions::{get_disruptions, DisruptionFilter};
+    use crate::models::disruption::DisruptionMessage;
+    use chrono::DateTime;
+
+    #[test]
+    fn test_filter_validity() {
+        let msg: DisruptionMessage = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "title": "Olten - Bern: Einschränkungen",
+                "affectedLines": ["IC 5"],
+                "validityPeriods": [{"start": "2026-02-22T10:00:00+01:00", "end": "2026-02-22T14:00:00+01:00"}]
+            }"#,
+        )
+        .unwrap();
+        let at = |t: &str| DisruptionFilter {
+            line: Some("IC 5".to_string()),
+            valid_at: Some(DateTime::parse_from_rfc3339(t).unwrap()),
+            ..Default::default()
+        };
+        assert!(at("2026-02-22T12:00:00+01:00").matches(&msg));
+        assert!(!at("2026-02-22T15:00:00+01:00").matches(&msg));
+        assert!(DisruptionFilter::default().matches(&msg));
+    }
+
+    #[actix_rt::test]
+    #[ignore = "requires live API access"]
+    pub async fn test_get_disruptions() {
+        let result = get_disruptions(&DisruptionFilter::default(), None).await;
+        assert!(result.is_ok(), "error: {:?}", result.err());
+        for msg in result.unwrap() {
+            println!("{}: {}", msg.id, msg.title);
+        }
+    }
+}
@@ -0,0 +1,386 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+
+use super::csv;
+use crate::models::trip::{parse_time, StopTime, Trip, TripLeg};
+use crate::overview::DaySummary;
+
+/// A record with a fixed set of columns, for tabular output.
+pub trait FlatRecord: Serialize {
+    const HEADERS: &'static [&'static str];
+
+    /// Column values, in `HEADERS` order. `human` shortens times to `HH:MM` and signs delays.
+    fn fields(&self, human: bool) -> Vec<String>;
+}
+
+/// One connection, flattened. `*_aimed` are scheduled times, `*_expected` real-time ones when known.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TripRow {
+    /// Position in the search results, starting at 1.
+    pub trip: usize,
+    pub departure_station: String,
+    pub departure_aimed: Option<DateTime<FixedOffset>>,
+    pub departure_expected: Option<DateTime<FixedOffset>>,
+    pub departure_delay: Option<i64>,
+    pub departure_quay: Option<String>,
+    pub arrival_station: String,
+    pub arrival_aimed: Option<DateTime<FixedOffset>>,
+    pub arrival_expected: Option<DateTime<FixedOffset>>,
+    pub arrival_delay: Option<i64>,
+    pub arrival_quay: Option<String>,
+    pub line: Option<String>,
+    pub duration_minutes: Option<i64>,
+    pub transfers: usize,
+    pub occupancy_first_class: Option<String>,
+    pub occupancy_second_class: Option<String>,
+    pub cancelled: bool,
+}
+
+/// One ride leg of a connection, flattened.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LegRow {
+    /// Position of the connection in the search results, starting at 1.
+    pub trip: usize,
+    /// Position of the leg within the connection, starting at 1 (walks and changes included).
+    pub leg: usize,
+    pub line: Option<String>,
+    pub direction: Option<String>,
+    pub departure_station: String,
+    pub departure_aimed: Option<DateTime<FixedOffset>>,
+    pub departure_expected: Option<DateTime<FixedOffset>>,
+    pub departure_delay: Option<i64>,
+    pub departure_quay: Option<String>,
+    pub arrival_station: String,
+    pub arrival_aimed: Option<DateTime<FixedOffset>>,
+    pub arrival_expected: Option<DateTime<FixedOffset>>,
+    pub arrival_delay: Option<i64>,
+    pub arrival_quay: Option<String>,
+    pub occupancy_first_class: Option<String>,
+    pub occupancy_second_class: Option<String>,
+    pub cancelled: bool,
+}
+
+impl Trip {
+    /// Flatten the connection into one row. `index` is its 1-based position in the results.
+    pub fn to_row(&self, index: usize) -> TripRow {
+        let summary = &self.summary;
+        let dep = &summary.departure_anchor;
+        let arr = &summary.arrival_anchor;
+        TripRow {
+            trip: index,
+            departure_station: summary.departure_display_name.clone(),
+            departure_aimed: parse_time(&dep.time_aimed),
+            departure_expected: parse_time(&dep.time_expected),
+            departure_delay: dep.delay_minutes(),
+            departure_quay: dep.quay_name().map(str::to_string),
+            arrival_station: summary.arrival_display_name.clone(),
+            arrival_aimed: parse_time(&arr.time_aimed),
+            arrival_expected: parse_time(&arr.time_expected),
+            arrival_delay: arr.delay_minutes(),
+            arrival_quay: arr.quay_name().map(str::to_string),
+            line: dep.transport_designation.as_ref().map(|t| t.to_string()),
+            duration_minutes: self.duration_minutes(),
+            transfers: self.transfers(),
+            occupancy_first_class: summary.occupancy_first_class_max.clone(),
+            occupancy_second_class: summary.occupancy_second_class_max.clone(),
+            cancelled: self.is_cancelled(),
+        }
+    }
+
+    /// Flatten every ride leg into a row. `index` is the 1-based position of the connection.
+    pub fn leg_rows(&self, index: usize) -> Vec<LegRow> {
+        let disruptions = self.disruptions();
+        let legs = self.detail.as_ref().map(|d| d.legs.as_slice()).unwrap_or_default();
+        legs.iter()
+            .enumerate()
+            .filter_map(|(idx, leg)| match leg {
+                TripLeg::PtRideLeg(pt) => {
+                    let dep = &pt.departure_stop_point;
+                    let arr = &pt.arrival_stop_point;
+                    Some(LegRow {
+                        trip: index,
+                        leg: idx + 1,
+                        line: pt.first_transport_designation.as_ref().map(|t| t.to_string()),
+                        direction: pt.direction.clone(),
+                        departure_station: dep.display_name.clone(),
+                        departure_aimed: aimed(&dep.departure_time),
+                        departure_expected: expected(&dep.departure_time),
+                        departure_delay: dep.departure_time.as_ref().and_then(StopTime::delay_minutes),
+                        departure_quay: dep.quay_name().map(str::to_string),
+                        arrival_station: arr.display_name.clone(),
+                        arrival_aimed: aimed(&arr.arrival_time),
+                        arrival_expected: expected(&arr.arrival_time),
+                        arrival_delay: arr.arrival_time.as_ref().and_then(StopTime::delay_minutes),
+                        arrival_quay: arr.quay_name().map(str::to_string),
+                        occupancy_first_class: dep.occupancy_first_class.clone(),
+                        occupancy_second_class: dep.occupancy_second_class.clone(),
+                        cancelled: disruptions
+                            .iter()
+                            .any(|d| d.rt_type.is_cancellation() && d.leg_index.is_none_or(|i| i == idx)),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// One row per connection, numbered from 1.
+pub fn trip_rows(trips: &[Trip]) -> Vec<TripRow> {
+    trips.iter().enumerate().map(|(i, t)| t.to_row(i + 1)).collect()
+}
+
+/// One row per ride leg of every connection.
+pub fn leg_rows(trips: &[Trip]) -> Vec<LegRow> {
+    trips.iter().enumerate().flat_map(|(i, t)| t.leg_rows(i + 1)).collect()
+}
+
+fn aimed(time: &Option<StopTime>) -> Option<DateTime<FixedOffset>> {
+    time.as_ref().and_then(|t| parse_time(&t.time_aimed))
+}
+
+fn expected(time: &Option<StopTime>) -> Option<DateTime<FixedOffset>> {
+    time.as_ref().and_then(|t| parse_time(&t.time_expected))
+}
+
+fn time_field(time: &Option<DateTime<FixedOffset>>, human: bool) -> String {
+    match time {
+        Some(t) if human => t.format("%H:%M").to_string(),
+        Some(t) => t.to_rfc3339(),
+        None => String::new(),
+    }
+}
+
+fn delay_field(delay: &Option<i64>, human: bool) -> String {
+    match delay {
+        Some(0) if human => String::new(),
+        Some(d) if human => format!("{:+}", d),
+        Some(d) => d.to_string(),
+        None => String::new(),
+    }
+}
+
+fn text_field<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(|v| v.to_string()).unwrap_or_default()
+}
+
+impl FlatRecord for TripRow {
+    const HEADERS: &'static [&'static str] = &[
+        "trip",
+        "departure_station",
+        "departure_aimed",
+        "departure_expected",
+        "departure_delay",
+        "departure_quay",
+        "arrival_station",
+        "arrival_aimed",
+        "arrival_expected",
+        "arrival_delay",
+        "arrival_quay",
+        "line",
+        "duration_minutes",
+        "transfers",
+        "occupancy_first_class",
+        "occupancy_second_class",
+        "cancelled",
+    ];
+
+    fn fields(&self, human: bool) -> Vec<String> {
+        vec![
+            self.trip.to_string(),
+            self.departure_station.clone(),
+            time_field(&self.departure_aimed, human),
+            time_field(&self.departure_expected, human),
+            delay_field(&self.departure_delay, human),
+            text_field(&self.departure_quay),
+            self.arrival_station.clone(),
+            time_field(&self.arrival_aimed, human),
+            time_field(&self.arrival_expected, human),
+            delay_field(&self.arrival_delay, human),
+            text_field(&self.arrival_quay),
+            text_field(&self.line),
+            text_field(&self.duration_minutes),
+            self.transfers.to_string(),
+            text_field(&self.occupancy_first_class),
+            text_field(&self.occupancy_second_class),
+            self.cancelled.to_string(),
+        ]
+    }
+}
+
+impl FlatRecord for LegRow {
+    const HEADERS: &'static [&'static str] = &[
+        "trip",
+        "leg",
+        "line",
+        "direction",
+        "departure_station",
+        "departure_aimed",
+        "departure_expected",
+        "departure_delay",
+        "departure_quay",
+        "arrival_station",
+        "arrival_aimed",
+        "arrival_expected",
+        "arrival_delay",
+        "arrival_quay",
+        "occupancy_first_class",
+        "occupancy_second_class",
+        "cancelled",
+    ];
+
+    fn fields(&self, human: bool) -> Vec<String> {
+        vec![
+            self.trip.to_string(),
+            self.leg.to_string(),
+            text_field(&self.line),
+            text_field(&self.direction),
+            self.departure_station.clone(),
+            time_field(&self.departure_aimed, human),
+            time_field(&self.departure_expected, human),
+            delay_field(&self.departure_delay, human),
+            text_field(&self.departure_quay),
+            self.arrival_station.clone(),
+            time_field(&self.arrival_aimed, human),
+            time_field(&self.arrival_expected, human),
+            delay_field(&self.arrival_delay, human),
+            text_field(&self.arrival_quay),
+            text_field(&self.occupancy_first_class),
+            text_field(&self.occupancy_second_class),
+            self.cancelled.to_string(),
+        ]
+    }
+}
+
+impl FlatRecord for DaySummary {
+    const HEADERS: &'static [&'static str] = &[
+        "date",
+        "departure",
+        "arrival",
+        "duration_minutes",
+        "transfers",
+        "line",
+        "disruptions",
+        "error",
+    ];
+
+    fn fields(&self, human: bool) -> Vec<String> {
+        vec![
+            if human { self.date.format("%a %d.%m.").to_string() } else { self.date.to_string() },
+            time_field(&self.departure, human),
+            time_field(&self.arrival, human),
+            text_field(&self.duration_minutes),
+            text_field(&self.transfers),
+            text_field(&self.line),
+            self.disruptions.join("; "),
+            text_field(&self.error),
+        ]
+    }
+}
+
+/// CSV with a header line.
+pub fn to_csv<R: FlatRecord>(rows: &[R]) -> String {
+    let mut out = csv::row(R::HEADERS) + "\n";
+    for r in rows {
+        out.push_str(&csv::row(&r.fields(false)));
+        out.push('\n');
+    }
+    out
+}
+
+/// One JSON object per line.
+pub fn to_ndjson<R: FlatRecord>(rows: &[R]) -> String {
+    rows.iter()
+        .map(|r| serde_json::to_string(r).expect("rows serialize to JSON") + "\n")
+        .collect()
+}
+
+/// A GitHub-flavored Markdown table.
+pub fn to_markdown<R: FlatRecord>(rows: &[R]) -> String {
+    let cell = |s: &str| s.replace('|', "\\|");
+    let mut out = format!("| {} |\n", R::HEADERS.join(" | "));
+    out.push_str(&format!("|{}\n", "---|".repeat(R::HEADERS.len())));
+    for r in rows {
+        let fields: Vec<String> = r.fields(true).iter().map(|f| cell(f)).collect();
+        out.push_str(&format!("| {} |\n", fields.join(" | ")));
+    }
+    out
+}
+
+/// A plain-text table with aligned columns.
+pub fn to_table<R: FlatRecord>(rows: &[R]) -> String {
+    let body: Vec<Vec<String>> = rows.iter().map(|r| r.fields(true)).collect();
+    let widths: Vec<usize> = R::HEADERS
+        .iter()
+        .enumerate()
+        .map(|(i, h)| body.iter().map(|r| r[i].chars().count()).fold(h.len(), usize::max))
+        .collect();
+
+    let line = |fields: &[String]| {
+        let cells: Vec<String> = fields
+            .iter()
+            .zip(&widths)
+            .map(|(f, w)| format!("{:w$}", f, w = w))
+            .collect();
+        cells.join("  ").trim_end().to_string() + "\n"
+    };
+
+    let headers: Vec<String> = R::HEADERS.iter().map(|h| h.to_string()).collect();
+    let mut out = line(&headers);
+    for r in &body {
+        out.push_str(&line(r));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+
+    #[test]
+    fn test_trip_rows() {
+        let trips = trips("sbb_api_response_0.json");
+        let rows = trip_rows(&trips);
+        assert_eq!(rows.len(), trips.len());
+        let first = &rows[0];
+        assert_eq!(first.trip, 1);
+        assert_eq!(first.departure_station, "Basel SBB");
+        assert_eq!(first.duration_minutes, Some(84));
+        assert_eq!(first.transfers, 1);
+        assert_eq!(first.fields(true)[2], "13:00");
+        assert_eq!(first.fields(false)[2], "2026-02-22T13:00:00+01:00");
+    }
+
+    #[test]
+    fn test_leg_rows() {
+        let trips = trips("sbb_api_response_0.json");
+        let rows = leg_rows(&trips[..1]);
+        assert_eq!(rows.len(), trips[0].ride_legs().count());
+        assert_eq!(rows[0].arrival_station, "Olten");
+        assert_eq!(rows.last().unwrap().arrival_station, "Bern");
+        assert!(rows.iter().all(|r| r.trip == 1));
+    }
+
+    #[test]
+    fn test_renderers() {
+        let rows = trip_rows(&trips("sbb_api_response_0.json")[..2]);
+
+        let csv = to_csv(&rows);
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.starts_with("trip,departure_station,"));
+
+        let ndjson = to_ndjson(&rows);
+        let parsed: serde_json::Value = serde_json::from_str(ndjson.lines().next().unwrap()).unwrap();
+        assert_eq!(parsed["transfers"], 1);
+
+        let markdown = to_markdown(&rows);
+        assert!(markdown.lines().nth(1).unwrap().starts_with("|---|"));
+        assert!(markdown.contains("| 1 | Basel SBB | 13:00 |"));
+
+        let table = to_table(&rows);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].find("departure_station"), lines[1].find("Basel SBB"));
+    }
+}
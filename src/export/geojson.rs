@@ -0,0 +1,160 @@
+use serde_json::{json, Map, Value};
+
+use crate::models::place::Coordinates;
+use crate::models::trip::{StopPoint, Trip, TripLeg};
+
+impl Trip {
+    /// Export the trip as a GeoJSON `FeatureCollection`.
+    ///
+    /// Every ride and walking leg with known geometry becomes a `LineString`, and every
+    /// leg's departure and arrival stop with known coordinates becomes a `Point`.
+    /// Trips without leg detail fall back to the summary anchors.
+    pub fn to_geojson(&self) -> Value {
+        feature_collection(self.geojson_features())
+    }
+
+    fn geojson_features(&self) -> Vec<Value> {
+        let mut features = Vec::new();
+
+        let legs = self.detail.as_ref().map(|d| d.legs.as_slice()).unwrap_or_default();
+        for (idx, leg) in legs.iter().enumerate() {
+            let mut props = Map::new();
+            props.insert("leg_index".into(), json!(idx));
+            match leg {
+                TripLeg::PtRideLeg(pt) => {
+                    props.insert("kind".into(), json!("ride"));
+                    props.insert(
+                        "line".into(),
+                        json!(pt.first_transport_designation.as_ref().map(|t| t.to_string())),
+                    );
+                    props.insert("direction".into(), json!(pt.direction));
+                    props.insert(
+                        "departure".into(),
+                        json!(pt.departure_stop_point.departure_time.as_ref().and_then(|t| t.time_aimed.clone())),
+                    );
+                    props.insert(
+                        "arrival".into(),
+                        json!(pt.arrival_stop_point.arrival_time.as_ref().and_then(|t| t.time_aimed.clone())),
+                    );
+                }
+                TripLeg::AccessLeg(_) => {
+                    props.insert("kind".into(), json!("walk"));
+                }
+                TripLeg::ChangeLeg(_) => {
+                    props.insert("kind".into(), json!("change"));
+                }
+            }
+
+            let (dep, arr) = leg.stop_points();
+            props.insert("from".into(), json!(dep.map(|s| s.display_name.as_str())));
+            props.insert("to".into(), json!(arr.map(|s| s.display_name.as_str())));
+
+            let geometry = self.leg_geometry(idx);
+            if geometry.len() >= 2 {
+                features.push(feature(line_string(&geometry), props));
+            }
+
+            for stop in [dep, arr].into_iter().flatten() {
+                if let Some(point) = stop_point_feature(stop, idx) {
+                    features.push(point);
+                }
+            }
+        }
+
+        if features.is_empty() {
+            let dep = &self.summary.departure_anchor;
+            let arr = &self.summary.arrival_anchor;
+            let anchors = [
+                (&dep.place_name, &dep.place_reference, &dep.place_coordinates),
+                (&arr.place_name, &arr.place_reference, &arr.place_coordinates),
+            ];
+            for (name, uic, coords) in anchors {
+                if let Some(c) = coords {
+                    let mut props = Map::new();
+                    props.insert("name".into(), json!(name));
+                    props.insert("uic".into(), json!(uic));
+                    features.push(feature(point(c), props));
+                }
+            }
+        }
+
+        features
+    }
+}
+
+/// Export several trips as one GeoJSON `FeatureCollection`.
+/// Each feature gets a `trip` property holding the index of its trip in `trips`.
+pub fn trips_to_geojson(trips: &[Trip]) -> Value {
+    let features = trips
+        .iter()
+        .enumerate()
+        .flat_map(|(i, trip)| {
+            trip.geojson_features().into_iter().map(move |mut f| {
+                f["properties"]["trip"] = json!(i);
+                f
+            })
+        })
+        .collect();
+    feature_collection(features)
+}
+
+fn stop_point_feature(stop: &StopPoint, leg_index: usize) -> Option<Value> {
+    let coords = stop.coordinates.as_ref()?;
+    let mut props = Map::new();
+    props.insert("name".into(), json!(stop.display_name));
+    props.insert("uic".into(), json!(stop.place_reference));
+    props.insert("quay".into(), json!(stop.quay.as_ref().map(|q| q.name.as_str())));
+    props.insert("leg_index".into(), json!(leg_index));
+    Some(feature(point(coords), props))
+}
+
+fn feature_collection(features: Vec<Value>) -> Value {
+    json!({ "type": "FeatureCollection", "features": features })
+}
+
+fn feature(geometry: Value, properties: Map<String, Value>) -> Value {
+    json!({ "type": "Feature", "geometry": geometry, "properties": properties })
+}
+
+// GeoJSON positions are [longitude, latitude]
+fn point(c: &Coordinates) -> Value {
+    json!({ "type": "Point", "coordinates": [c.longitude, c.latitude] })
+}
+
+fn line_string(coords: &[Coordinates]) -> Value {
+    let positions: Vec<[f64; 2]> = coords.iter().map(|c| [c.longitude, c.latitude]).collect();
+    json!({ "type": "LineString", "coordinates": positions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+
+    #[test]
+    fn test_trip_to_geojson() {
+        let geojson = trips("sbb_api_response_0.json")[0].to_geojson();
+        assert_eq!(geojson["type"], "FeatureCollection");
+        let features = geojson["features"].as_array().unwrap();
+
+        // One line per ride leg; the change leg has no geometry
+        let lines: Vec<&Value> = features
+            .iter()
+            .filter(|f| f["geometry"]["type"] == "LineString")
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["properties"]["from"], "Basel SBB");
+        assert_eq!(lines[0]["properties"]["kind"], "ride");
+
+        // Longitude first
+        let first = &lines[0]["geometry"]["coordinates"][0];
+        assert!((first[0].as_f64().unwrap() - 7.589548).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_trips_to_geojson_tags_trip_index() {
+        let geojson = trips_to_geojson(&trips("sbb_api_response_3.json"));
+        let features = geojson["features"].as_array().unwrap();
+        assert!(features.iter().any(|f| f["properties"]["trip"] == 1));
+    }
+}
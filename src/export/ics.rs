@@ -0,0 +1,285 @@
+use chrono::{DateTime, FixedOffset, Utc};
+
+use crate::models::trip::{StopPoint, Trip, TripLeg};
+
+/// Time zone of all exported events. Times from the API are Swiss local times.
+pub const TZID: &str = "Europe/Zurich";
+
+/// CET/CEST rules for `Europe/Zurich`, as calendar clients expect a `VTIMEZONE` for every `TZID`.
+const VTIMEZONE: &[&str] = &[
+    "BEGIN:VTIMEZONE",
+    "TZID:Europe/Zurich",
+    "BEGIN:DAYLIGHT",
+    "TZOFFSETFROM:+0100",
+    "TZOFFSETTO:+0200",
+    "TZNAME:CEST",
+    "DTSTART:19810329T020000",
+    "RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU",
+    "END:DAYLIGHT",
+    "BEGIN:STANDARD",
+    "TZOFFSETFROM:+0200",
+    "TZOFFSETTO:+0100",
+    "TZNAME:CET",
+    "DTSTART:19961027T030000",
+    "RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU",
+    "END:STANDARD",
+    "END:VTIMEZONE",
+];
+
+/// How a trip is split into calendar events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcsEvents {
+    /// One event from departure to arrival, with the legs in the description.
+    PerTrip,
+    /// One event per ride leg.
+    PerLeg,
+}
+
+impl Trip {
+    /// Export the trip as an iCalendar (RFC 5545) document.
+    pub fn to_ics(&self, events: IcsEvents) -> String {
+        trips_to_ics(std::slice::from_ref(self), events)
+    }
+}
+
+/// Export several trips into one iCalendar document.
+pub fn trips_to_ics(trips: &[Trip], events: IcsEvents) -> String {
+    calendar(trips, events, Utc::now())
+}
+
+fn calendar(trips: &[Trip], events: IcsEvents, stamp: DateTime<Utc>) -> String {
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//sbb-api-rs//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    lines.extend(VTIMEZONE.iter().map(|l| l.to_string()));
+
+    for trip in trips {
+        match events {
+            IcsEvents::PerTrip => lines.extend(trip_event(trip, stamp)),
+            IcsEvents::PerLeg => {
+                let legs = trip.detail.as_ref().map(|d| d.legs.as_slice()).unwrap_or_default();
+                for (idx, leg) in legs.iter().enumerate() {
+                    if let TripLeg::PtRideLeg(pt) = leg {
+                        let line = pt.first_transport_designation.as_ref().map(|t| t.to_string());
+                        let dep = &pt.departure_stop_point;
+                        let arr = &pt.arrival_stop_point;
+                        let summary = match &line {
+                            Some(l) => format!("{} {} → {}", l, dep.display_name, arr.display_name),
+                            None => format!("{} → {}", dep.display_name, arr.display_name),
+                        };
+                        let mut description = vec![describe_leg(leg).unwrap_or_default()];
+                        description.extend(pt.direction.as_ref().map(|d| format!("Direction {}", d)));
+                        lines.extend(event(
+                            &format!("{}-{}", uid(&trip.meta.id), idx),
+                            stamp,
+                            dep.departure_time.as_ref().and_then(|t| t.time()),
+                            arr.arrival_time.as_ref().and_then(|t| t.time()),
+                            &summary,
+                            &stop_location(dep),
+                            &description.join("\n"),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|l| fold(l)).collect::<Vec<String>>().join("\r\n") + "\r\n"
+}
+
+fn trip_event(trip: &Trip, stamp: DateTime<Utc>) -> Vec<String> {
+    let summary = &trip.summary;
+    let mut title = format!("{} → {}", summary.departure_display_name, summary.arrival_display_name);
+    if let Some(t) = &summary.departure_anchor.transport_designation {
+        title.push_str(&format!(" ({})", t));
+    }
+
+    let mut location = summary.departure_anchor.place_name.clone();
+    if let Some(q) = summary.departure_anchor.quay.as_ref().filter(|q| !q.name.is_empty()) {
+        location.push_str(&format!(", {}", q.name));
+    }
+
+    let legs = trip.detail.as_ref().map(|d| d.legs.as_slice()).unwrap_or_default();
+    let description: Vec<String> = legs.iter().filter_map(describe_leg).collect();
+
+    event(
+        &uid(&trip.meta.id),
+        stamp,
+        trip.departure_time(),
+        trip.arrival_time(),
+        &title,
+        &location,
+        &description.join("\n"),
+    )
+}
+
+fn event(
+    uid: &str,
+    stamp: DateTime<Utc>,
+    start: Option<DateTime<FixedOffset>>,
+    end: Option<DateTime<FixedOffset>>,
+    summary: &str,
+    location: &str,
+    description: &str,
+) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}@sbb-api-rs", uid),
+        format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")),
+    ];
+    if let Some(start) = start {
+        lines.push(format!("DTSTART;TZID={}:{}", TZID, local(start)));
+    }
+    if let Some(end) = end {
+        lines.push(format!("DTEND;TZID={}:{}", TZID, local(end)));
+    }
+    lines.push(format!("SUMMARY:{}", escape(summary)));
+    if !location.is_empty() {
+        lines.push(format!("LOCATION:{}", escape(location)));
+    }
+    if !description.is_empty() {
+        lines.push(format!("DESCRIPTION:{}", escape(description)));
+    }
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+/// One description line per leg, e.g. "13:00 Basel SBB (Gl. 7) → 13:40 Olten (Gl. 4), S 3".
+fn describe_leg(leg: &TripLeg) -> Option<String> {
+    match leg {
+        TripLeg::PtRideLeg(pt) => {
+            let dep = &pt.departure_stop_point;
+            let arr = &pt.arrival_stop_point;
+            let mut text = format!(
+                "{} → {}",
+                stop_text(dep, dep.departure_time.as_ref().and_then(|t| t.time())),
+                stop_text(arr, arr.arrival_time.as_ref().and_then(|t| t.time())),
+            );
+            if let Some(t) = &pt.first_transport_designation {
+                text.push_str(&format!(", {}", t));
+            }
+            Some(text)
+        }
+        TripLeg::AccessLeg(_) => {
+            let (dep, arr) = leg.stop_points();
+            Some(format!("Walk {} → {}", dep?.display_name, arr?.display_name))
+        }
+        TripLeg::ChangeLeg(_) => None,
+    }
+}
+
+fn stop_text(stop: &StopPoint, time: Option<DateTime<FixedOffset>>) -> String {
+    let mut text = match time {
+        Some(t) => format!("{} {}", t.format("%H:%M"), stop.display_name),
+        None => stop.display_name.clone(),
+    };
+    if let Some(q) = stop.quay.as_ref().filter(|q| !q.name.is_empty()) {
+        text.push_str(&format!(" ({})", q.name));
+    }
+    text
+}
+
+fn stop_location(stop: &StopPoint) -> String {
+    match stop.quay.as_ref().filter(|q| !q.name.is_empty()) {
+        Some(q) => format!("{}, {}", stop.display_name, q.name),
+        None => stop.display_name.clone(),
+    }
+}
+
+fn local(time: DateTime<FixedOffset>) -> String {
+    time.naive_local().format("%Y%m%dT%H%M%S").to_string()
+}
+
+/// Stable short identifier for a trip: trip ids are opaque and over a kilobyte long.
+fn uid(trip_id: &str) -> String {
+    // FNV-1a, so UIDs don't change between builds and re-exports update the same event
+    let hash = trip_id
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3));
+    format!("{:016x}", hash)
+}
+
+/// Escape a TEXT value (RFC 5545, 3.3.11).
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line to at most 75 octets per line (RFC 5545, 3.1).
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut len = 0;
+    for c in line.chars() {
+        if len + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            len = 1;
+        }
+        out.push(c);
+        len += c.len_utf8();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+    use chrono::TimeZone;
+
+    fn unfold(ics: &str) -> String {
+        ics.replace("\r\n ", "")
+    }
+
+    #[test]
+    fn test_trip_event() {
+        let stamp = Utc.with_ymd_and_hms(2026, 2, 20, 9, 0, 0).unwrap();
+        let ics = calendar(&trips("sbb_api_response_0.json")[..1], IcsEvents::PerTrip, stamp);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.lines().all(|l| l.len() <= 76), "lines must be folded");
+
+        let ics = unfold(&ics);
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("DTSTAMP:20260220T090000Z"));
+        assert!(ics.contains("DTSTART;TZID=Europe/Zurich:20260222T130000"));
+        assert!(ics.contains("DTEND;TZID=Europe/Zurich:20260222T142400"));
+        assert!(ics.contains("TZID:Europe/Zurich\r\n"));
+        assert!(ics.contains("SUMMARY:Basel SBB → Bern"));
+        assert!(ics.contains("DESCRIPTION:13:00 Basel SBB"));
+        assert!(ics.contains("\\n13:56 Olten"));
+        // Quay names come with their prefix
+        assert!(ics.contains("LOCATION:Basel SBB\\, Gl. "));
+        assert!(!ics.contains("Pl. "));
+    }
+
+    #[test]
+    fn test_leg_events() {
+        let trips = trips("sbb_api_response_0.json");
+        let ics = unfold(&calendar(&trips[..1], IcsEvents::PerLeg, Utc::now()));
+        let rides = trips[0].ride_legs().count();
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), rides);
+        assert!(ics.contains("DTEND;TZID=Europe/Zurich:20260222T134000"));
+        assert!(ics.contains("LOCATION:Olten"));
+
+        // UIDs stay the same across exports
+        let again = unfold(&trips[0].to_ics(IcsEvents::PerLeg));
+        let uids = |s: &str| s.lines().filter(|l| l.starts_with("UID:")).map(str::to_string).collect::<Vec<_>>();
+        assert_eq!(uids(&ics), uids(&again));
+    }
+
+    #[test]
+    fn test_escape_and_fold() {
+        assert_eq!(escape("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+        let long = format!("SUMMARY:{}", "ü".repeat(60));
+        let folded = fold(&long);
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), long);
+    }
+}
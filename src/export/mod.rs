@@ -0,0 +1,4 @@
+pub mod csv;
+pub mod flat;
+pub mod geojson;
+pub mod ics;
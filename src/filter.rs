@@ -0,0 +1,153 @@
+use crate::models::trip::Trip;
+
+/// Criteria a trip must meet. Unset fields don't restrict anything.
+#[derive(Debug, Clone, Default)]
+pub struct TripFilter {
+    /// Maximum number of transfers; `Some(0)` keeps only direct connections.
+    pub max_transfers: Option<usize>,
+    /// Minimum time in minutes at every change.
+    pub min_change_minutes: Option<i64>,
+    /// Minimum change time at specific stations, overriding `min_change_minutes` there.
+    /// Station names are compared case-insensitively.
+    pub min_change_at: Vec<(String, i64)>,
+    /// Only allow rides of these product categories (e.g. "IC", "IR", "S"). Empty allows all.
+    pub categories: Vec<String>,
+    /// Reject rides of these product categories.
+    pub excluded_categories: Vec<String>,
+    /// Reject trips departing from, arriving at, changing at or passing through these
+    /// stations, given by name or UIC reference.
+    pub excluded_stations: Vec<String>,
+}
+
+impl TripFilter {
+    /// Whether `trip` meets every criterion.
+    pub fn matches(&self, trip: &Trip) -> bool {
+        self.transfers_ok(trip) && self.changes_ok(trip) && self.categories_ok(trip) && self.stations_ok(trip)
+    }
+
+    /// Keep only the trips matching this filter.
+    pub fn apply(&self, trips: &mut Vec<Trip>) {
+        trips.retain(|t| self.matches(t));
+    }
+
+    fn transfers_ok(&self, trip: &Trip) -> bool {
+        self.max_transfers.map(|max| trip.transfers() <= max).unwrap_or(true)
+    }
+
+    fn changes_ok(&self, trip: &Trip) -> bool {
+        trip.change_times().iter().all(|change| {
+            let required = self
+                .min_change_at
+                .iter()
+                .find(|(station, _)| station.to_lowercase() == change.station.to_lowercase())
+                .map(|(_, minutes)| *minutes)
+                .or(self.min_change_minutes);
+            match (required, change.minutes) {
+                (Some(required), Some(minutes)) => minutes >= required,
+                // Unknown change times can't be verified
+                (Some(_), None) => false,
+                (None, _) => true,
+            }
+        })
+    }
+
+    fn categories_ok(&self, trip: &Trip) -> bool {
+        trip.ride_legs().all(|leg| {
+            let category = leg.first_transport_designation.as_ref().map(|t| t.category());
+            let in_list = |list: &[String]| {
+                category
+                    .as_deref()
+                    .map(|c| list.iter().any(|l| l.eq_ignore_ascii_case(c)))
+                    .unwrap_or(false)
+            };
+            (self.categories.is_empty() || in_list(&self.categories)) && !in_list(&self.excluded_categories)
+        })
+    }
+
+    fn stations_ok(&self, trip: &Trip) -> bool {
+        if self.excluded_stations.is_empty() {
+            return true;
+        }
+        let excluded = |name: Option<&str>, reference: Option<&str>| {
+            self.excluded_stations.iter().any(|s| {
+                name.map(|n| n.to_lowercase() == s.to_lowercase()).unwrap_or(false) || reference == Some(s.as_str())
+            })
+        };
+
+        let summary = &trip.summary;
+        let anchors_hit = excluded(
+            Some(&summary.departure_anchor.place_name),
+            summary.departure_anchor.place_reference.as_deref(),
+        ) || excluded(
+            Some(&summary.arrival_anchor.place_name),
+            summary.arrival_anchor.place_reference.as_deref(),
+        );
+        let legs_hit = trip.ride_legs().any(|leg| {
+            [&leg.departure_stop_point, &leg.arrival_stop_point]
+                .iter()
+                .any(|s| excluded(Some(&s.display_name), s.place_reference.as_deref()))
+        });
+        // Intermediate stops are only known by UIC reference
+        let passes_hit = trip
+            .companion
+            .iter()
+            .flat_map(|c| c.cards.iter())
+            .flat_map(|card| card.stops_metadata.iter())
+            .any(|s| excluded(None, s.place_reference.as_deref()));
+
+        !(anchors_hit || legs_hit || passes_hit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+
+    #[test]
+    fn test_direct_only() {
+        let filter = TripFilter { max_transfers: Some(0), ..Default::default() };
+        let mut basel_bern = trips("sbb_api_response_0.json");
+        filter.apply(&mut basel_bern);
+        assert!(basel_bern.iter().all(|t| t.transfers() == 0));
+
+        let mut zurich_chur = trips("sbb_api_response_3.json");
+        let first = zurich_chur[0].meta.id.clone();
+        filter.apply(&mut zurich_chur);
+        assert_eq!(zurich_chur[0].meta.id, first);
+        assert!(zurich_chur.iter().all(|t| t.transfers() == 0));
+    }
+
+    #[test]
+    fn test_min_change_time() {
+        // The first Basel SBB -> Bern trip has a 16 minute change at Olten
+        let trip = &trips("sbb_api_response_0.json")[0];
+
+        let filter = |global, at: Vec<(&str, i64)>| TripFilter {
+            min_change_minutes: global,
+            min_change_at: at.into_iter().map(|(s, m)| (s.to_string(), m)).collect(),
+            ..Default::default()
+        };
+        assert!(filter(Some(6), vec![]).matches(trip));
+        assert!(!filter(Some(20), vec![]).matches(trip));
+        assert!(!filter(None, vec![("olten", 20)]).matches(trip));
+        // Station-specific minimum wins over the global one
+        assert!(filter(Some(20), vec![("Olten", 10)]).matches(trip));
+        assert!(filter(None, vec![("Bern", 20)]).matches(trip));
+    }
+
+    #[test]
+    fn test_categories_and_stations() {
+        let trip = &trips("sbb_api_response_0.json")[0];
+
+        let only_ic = TripFilter { categories: vec!["IC".to_string()], ..Default::default() };
+        assert!(!only_ic.matches(trip), "first leg is an S3");
+        let no_s = TripFilter { excluded_categories: vec!["s".to_string()], ..Default::default() };
+        assert!(!no_s.matches(trip));
+
+        let not_olten = TripFilter { excluded_stations: vec!["Olten".to_string()], ..Default::default() };
+        assert!(!not_olten.matches(trip));
+        let not_zurich = TripFilter { excluded_stations: vec!["8503000".to_string()], ..Default::default() };
+        assert!(not_zurich.matches(trip));
+    }
+}
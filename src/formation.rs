@@ -0,0 +1,49 @@
+use chrono::NaiveDate;
+use simple_error::{bail, SimpleError};
+
+use crate::{get_json, Language};
+use crate::models::formation::Formation;
+use crate::models::trip::PtRideLeg;
+
+/// Fetch the train composition of a ride leg at its departure stop.
+///
+/// Only legs returned with a `formation_path` (usually long-distance trains) have one.
+pub async fn get_leg_formation(leg: &PtRideLeg, language: Option<Language>) -> Result<Formation, SimpleError> {
+    let path = match &leg.formation_path {
+        Some(p) => p,
+        None => bail!("No formation available for this leg"),
+    };
+    // The API returns the path without its leading slash
+    get_json(&format!("/{}", path.trim_start_matches('/')), &[], language).await
+}
+
+/// Fetch the composition of train `train_number` (e.g. `"519"`) on `date`.
+///
+/// `stop_ref` selects the UIC station at which sectors are reported; the origin is used when omitted.
+pub async fn get_formation(
+    train_number: &str,
+    date: &NaiveDate,
+    stop_ref: Option<&str>,
+    language: Option<Language>,
+) -> Result<Formation, SimpleError> {
+    let date = date.format("%Y-%m-%d").to_string();
+    let mut params: Vec<(&str, &str)> = vec![("trainNumber", train_number), ("date", &date)];
+    if let Some(r) = stop_ref {
+        params.push(("stopReference", r));
+    }
+    get_json("/api/timetable/v2/formations", &params, language).await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::formation::get_formation;
+
+    #[actix_rt::test]
+    #[ignore = "requires live API access"]
+    pub async fn test_get_formation_by_train_number() {
+        let today = chrono::offset::Local::now().date_naive();
+        let result = get_formation("519", &today, Some("8503000"), None).await;
+        assert!(result.is_ok(), "error: {:?}", result.err());
+        println!("{} coaches", result.unwrap().coaches.len());
+    }
+}
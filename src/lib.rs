@@ -1,18 +1,77 @@
+pub mod board;
 pub mod connections;
+pub mod datetime;
+pub mod offers;
+pub mod diff;
+pub mod disruptions;
+pub mod filter;
+pub mod formation;
+pub mod matrix;
+pub mod meeting;
+pub mod overview;
+pub mod places;
+pub mod planner;
+pub mod ranking;
+pub mod reachability;
+pub mod service_day;
+pub mod template;
+pub mod watch;
 pub mod authenticator;
 pub mod models;
+pub mod export;
 
 use reqwest::{Certificate, Method, Url, Response};
-use reqwest::header::{HeaderMap, USER_AGENT, HeaderValue, HeaderName};
+use reqwest::header::{HeaderMap, ACCEPT_LANGUAGE, USER_AGENT, HeaderValue, HeaderName};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use simple_error::{bail, SimpleError};
+use std::fmt;
 use std::str::FromStr;
 
 pub const API_ENDPOINT: &str = "https://active.vnext.app.sbb.ch";
 pub const SBB_UA: &str = "SBBmobile/12.49.5.166.master Android/14 (Google;Pixel 8;android14)";
 
+/// Language of the texts returned by the API (disruption messages, labels, ...).
+///
+/// Requests take an `Option<Language>`; with `None`, the API answers in German.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    De,
+    Fr,
+    It,
+    En,
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::De => write!(f, "de"),
+            Language::Fr => write!(f, "fr"),
+            Language::It => write!(f, "it"),
+            Language::En => write!(f, "en"),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = SimpleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "de" => Ok(Language::De),
+            "fr" => Ok(Language::Fr),
+            "it" => Ok(Language::It),
+            "en" => Ok(Language::En),
+            other => bail!("unknown language '{}', expected de, fr, it or en", other),
+        }
+    }
+}
+
 /// SBB's self-signed root CA (*.sbbmobile.ch). Required to verify the API server certificate.
 const SBB_CA_CERT: &[u8] = include_bytes!("../resources/ca_cert.crt");
 
-pub(crate) async fn make_request(url: Url, path: &str) -> Result<Response, reqwest::Error> {
+pub(crate) async fn make_request(url: Url, path: &str, language: Option<Language>) -> Result<Response, reqwest::Error> {
     let mut headers = HeaderMap::new();
     headers.append(
         USER_AGENT,
@@ -22,6 +81,12 @@ pub(crate) async fn make_request(url: Url, path: &str) -> Result<Response, reqwe
         HeaderName::from_str("USE-CASE").expect("Invalid header name"),
         HeaderValue::from_static("TIMETABLE"),
     );
+    if let Some(language) = language {
+        headers.append(
+            ACCEPT_LANGUAGE,
+            HeaderValue::from_str(&language.to_string()).expect("Invalid header value"),
+        );
+    }
 
     // App token - random UUID
     let app_token = authenticator::generate_app_token();
@@ -53,3 +118,34 @@ pub(crate) async fn make_request(url: Url, path: &str) -> Result<Response, reqwe
     let request = reqwest::Request::new(Method::GET, url);
     client.execute(request).await
 }
+
+/// Perform a signed GET request against `path` and decode the JSON response body.
+///
+/// Texts are in `language`, or German without one.
+/// Non-2xx responses are reported as `"HTTP {status}: {body}"` errors.
+pub(crate) async fn get_json<T: DeserializeOwned>(
+    path: &str,
+    params: &[(&str, &str)],
+    language: Option<Language>,
+) -> Result<T, SimpleError> {
+    let url = Url::parse_with_params(&format!("{}{}", API_ENDPOINT, path), params)
+        .map_err(|e| SimpleError::new(format!("URL parse error: {}", e)))?;
+
+    let resp = make_request(url, path, language)
+        .await
+        .map_err(|e| SimpleError::new(format!("Request error: {}", e)))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        bail!("HTTP {}: {}", status, body)
+    }
+
+    let text = resp
+        .text()
+        .await
+        .map_err(|e| SimpleError::new(format!("Response read error: {}", e)))?;
+
+    serde_json::from_str(&text)
+        .map_err(|e| SimpleError::new(format!("JSON parse error: {}", e)))
+}
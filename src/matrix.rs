@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+
+use crate::connections::ConnectionCache;
+use crate::export::csv;
+use crate::models::location::StationRef;
+use crate::reachability::{fastest_to, RankBy, ReachabilityOptions};
+
+/// Fastest connection between two stations of a [`TravelTimeMatrix`].
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixCell {
+    pub duration_minutes: i64,
+    /// Transfers of the fastest connection.
+    pub transfers: usize,
+}
+
+/// Travel times between every pair of `stations`.
+///
+/// `cells[i][j]` is the trip from `stations[i]` to `stations[j]`; the diagonal is always
+/// `None`, as are pairs without a connection in the window or whose search failed.
+#[derive(Serialize, Debug, Clone)]
+pub struct TravelTimeMatrix {
+    pub stations: Vec<StationRef>,
+    pub cells: Vec<Vec<Option<MatrixCell>>>,
+    /// Errors of failed searches as `(from index, to index, message)`.
+    pub errors: Vec<(usize, usize, String)>,
+}
+
+impl TravelTimeMatrix {
+    pub fn get(&self, from: usize, to: usize) -> Option<MatrixCell> {
+        self.cells.get(from)?.get(to).copied().flatten()
+    }
+
+    /// Render as CSV: a header row of destinations, then one row per origin. Each cell
+    /// holds the travel time and the transfers, e.g. `84/1`; missing cells are left empty.
+    pub fn to_csv(&self) -> String {
+        let names: Vec<String> = self.stations.iter().map(|s| s.to_string()).collect();
+        let mut header = vec!["from \\ to".to_string()];
+        header.extend(names.iter().cloned());
+
+        let mut lines = vec![csv::row(&header)];
+        for (i, row) in self.cells.iter().enumerate() {
+            let mut fields = vec![names[i].clone()];
+            fields.extend(row.iter().map(|cell| {
+                cell.map(|c| format!("{}/{}", c.duration_minutes, c.transfers)).unwrap_or_default()
+            }));
+            lines.push(csv::row(&fields));
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Compute the fastest connection between every ordered pair of `stations`, departing
+/// between `window_start` and `window_end` (local wall-clock times, see [`crate::reachability::reachability`]).
+///
+/// Cells hold the shortest ride, whatever `options.rank_by` says. Runs the N×(N-1)
+/// searches with at most `options.max_concurrency` requests in flight.
+pub async fn travel_time_matrix(
+    stations: &[StationRef],
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+    options: &ReachabilityOptions,
+    cache: &ConnectionCache,
+) -> TravelTimeMatrix {
+    let options = &ReachabilityOptions { rank_by: RankBy::Duration, ..*options };
+    let n = stations.len();
+    let pairs: Vec<(usize, usize)> = (0..n)
+        .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+        .collect();
+
+    let results: Vec<_> = stream::iter(pairs)
+        .map(|(i, j)| async move {
+            let result = fastest_to(&stations[i], &stations[j], window_start, window_end, options, cache).await;
+            (i, j, result)
+        })
+        .buffer_unordered(options.max_concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut matrix = TravelTimeMatrix {
+        stations: stations.to_vec(),
+        cells: vec![vec![None; n]; n],
+        errors: Vec::new(),
+    };
+    for (i, j, result) in results {
+        match result {
+            Ok(fastest) => {
+                matrix.cells[i][j] = fastest.map(|f| MatrixCell {
+                    duration_minutes: f.duration_minutes,
+                    transfers: f.transfers,
+                });
+            }
+            Err(e) => matrix.errors.push((i, j, e.to_string())),
+        }
+    }
+    matrix.errors.sort();
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_csv() {
+        let cell = |d, t| Some(MatrixCell { duration_minutes: d, transfers: t });
+        let matrix = TravelTimeMatrix {
+            stations: vec![StationRef::new("Zürich HB", None), StationRef::new("Bern", None), "8500010".parse().unwrap()],
+            cells: vec![
+                vec![None, cell(56, 0), cell(53, 0)],
+                vec![cell(58, 0), None, None],
+                vec![cell(54, 0), cell(55, 1), None],
+            ],
+            errors: Vec::new(),
+        };
+
+        assert_eq!(
+            matrix.to_csv(),
+            "from \\ to,Zürich HB,Bern,8500010\nZürich HB,,56/0,53/0\nBern,58/0,,\n8500010,54/0,55/1,\n"
+        );
+        assert_eq!(matrix.get(2, 1).map(|c| c.transfers), Some(1));
+        assert_eq!(matrix.get(1, 2), None);
+        assert_eq!(matrix.get(5, 0), None);
+    }
+
+    #[actix_rt::test]
+    async fn test_matrix_window_is_local_time() {
+        use crate::models::location::SearchDateTimeType;
+        use crate::models::trip::fixtures::response;
+        use chrono::TimeZone;
+
+        let resp = response("sbb_api_response_2.json");
+        let stations = vec![StationRef::new("Genève", Some("8501008")), StationRef::new("Zürich HB", Some("8503000"))];
+        // `sbb matrix --at 12:00`: the 12:05+01:00 departure is within the first five minutes
+        let start = Utc.with_ymd_and_hms(2026, 2, 22, 12, 0, 0).unwrap();
+        let end = start + chrono::Duration::minutes(5);
+
+        let cache = ConnectionCache::new();
+        cache.insert(&stations[0], &stations[1], &start, SearchDateTimeType::Departure, resp.clone());
+        cache.insert(&stations[1], &stations[0], &start, SearchDateTimeType::Departure, resp);
+        let matrix = travel_time_matrix(&stations, start, end, &ReachabilityOptions::default(), &cache).await;
+
+        assert!(matrix.errors.is_empty(), "{:?}", matrix.errors);
+        assert_eq!(matrix.get(0, 1).map(|c| c.duration_minutes), Some(171));
+        assert_eq!(matrix.get(1, 0).map(|c| c.duration_minutes), Some(171));
+        // Nothing outside the seeded searches was asked for
+        assert_eq!(cache.len(), 2);
+    }
+}
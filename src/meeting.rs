@@ -0,0 +1,250 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+
+use crate::export::csv;
+use crate::models::location::StationRef;
+use crate::models::trip::Trip;
+use crate::planner::{plan_arrive_by, ArriveByOptions};
+
+/// How one origin gets to a meeting station.
+#[derive(Serialize, Debug, Clone)]
+pub struct MeetingLeg {
+    pub origin: StationRef,
+    /// When this origin leaves and arrives at the meeting station, real-time where known;
+    /// both `None` when already there or when nothing arrives in time.
+    pub departure: Option<DateTime<FixedOffset>>,
+    pub arrival: Option<DateTime<FixedOffset>>,
+    /// Travel time; 0 when the origin is the meeting station itself, `None` when
+    /// no connection arrives in time or the search failed.
+    pub duration_minutes: Option<i64>,
+    pub transfers: Option<usize>,
+    /// Error of the arrive-by search from this origin; such a leg makes the candidate
+    /// unreachable rather than failing the whole search.
+    pub error: Option<String>,
+}
+
+impl MeetingLeg {
+    /// The leg for the connection found from `origin`, if any.
+    pub fn from_trip(origin: &StationRef, trip: Option<&Trip>) -> MeetingLeg {
+        MeetingLeg {
+            origin: origin.clone(),
+            departure: trip.and_then(|t| t.departure_time()),
+            arrival: trip.and_then(|t| t.arrival_time()),
+            duration_minutes: trip.and_then(|t| t.duration_minutes()),
+            transfers: trip.map(|t| t.transfers()),
+            error: None,
+        }
+    }
+
+    /// The leg of someone already at the meeting station.
+    fn staying(origin: &StationRef) -> MeetingLeg {
+        MeetingLeg {
+            duration_minutes: Some(0),
+            transfers: Some(0),
+            ..MeetingLeg::from_trip(origin, None)
+        }
+    }
+
+    fn failed(origin: &StationRef, error: String) -> MeetingLeg {
+        MeetingLeg {
+            error: Some(error),
+            ..MeetingLeg::from_trip(origin, None)
+        }
+    }
+}
+
+/// A candidate meeting station with the way there from every origin.
+#[derive(Serialize, Debug, Clone)]
+pub struct MeetingCandidate {
+    pub station: StationRef,
+    /// One leg per origin, in the order of the origins.
+    pub legs: Vec<MeetingLeg>,
+    /// Longest and summed travel time over all origins; `None` unless every origin
+    /// gets there in time.
+    pub max_minutes: Option<i64>,
+    pub total_minutes: Option<i64>,
+}
+
+impl MeetingCandidate {
+    pub fn new(station: StationRef, legs: Vec<MeetingLeg>) -> MeetingCandidate {
+        let durations: Option<Vec<i64>> = legs.iter().map(|l| l.duration_minutes).collect();
+        MeetingCandidate {
+            station,
+            legs,
+            max_minutes: durations.as_ref().and_then(|d| d.iter().copied().max()),
+            total_minutes: durations.map(|d| d.iter().sum()),
+        }
+    }
+
+    /// Whether every origin gets there in time
+    pub fn is_reachable(&self) -> bool {
+        self.max_minutes.is_some()
+    }
+}
+
+/// Candidate meeting stations, best first.
+#[derive(Serialize, Debug, Clone)]
+pub struct MeetingPoints {
+    pub origins: Vec<StationRef>,
+    pub candidates: Vec<MeetingCandidate>,
+}
+
+impl MeetingPoints {
+    /// Rank candidates by longest travel time, then by total travel time. Candidates
+    /// not every origin can reach come last, in their original order.
+    pub fn rank(&mut self) {
+        self.candidates.sort_by_key(|c| (!c.is_reachable(), c.max_minutes, c.total_minutes));
+    }
+
+    /// Render as CSV: one row per candidate, best first, with the travel time from each
+    /// origin. Missing travel times are left empty.
+    pub fn to_csv(&self) -> String {
+        let minutes = |m: Option<i64>| m.map(|m| m.to_string()).unwrap_or_default();
+        let mut header = vec!["rank".to_string(), "station".to_string(), "max_minutes".to_string(), "total_minutes".to_string()];
+        header.extend(self.origins.iter().map(|o| o.to_string()));
+
+        let mut lines = vec![csv::row(&header)];
+        for (i, candidate) in self.candidates.iter().enumerate() {
+            let mut fields = vec![
+                (i + 1).to_string(),
+                candidate.station.to_string(),
+                minutes(candidate.max_minutes),
+                minutes(candidate.total_minutes),
+            ];
+            fields.extend(candidate.legs.iter().map(|l| minutes(l.duration_minutes)));
+            lines.push(csv::row(&fields));
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Whether two references name the same station: by UIC reference when both have one,
+/// else by name.
+fn same_station(a: &StationRef, b: &StationRef) -> bool {
+    match (&a.reference, &b.reference) {
+        (Some(x), Some(y)) => x == y,
+        _ => !a.name.is_empty() && a.name.to_lowercase() == b.name.to_lowercase(),
+    }
+}
+
+/// Find where `origins` can best meet by `deadline` (local time) among `candidates`.
+///
+/// Plans an arrive-by trip from every origin to every candidate (see [`plan_arrive_by`]),
+/// with at most `max_concurrency` requests in flight, and ranks the candidates with
+/// [`MeetingPoints::rank`].
+pub async fn find_meeting_point(
+    origins: &[StationRef],
+    candidates: &[StationRef],
+    deadline: NaiveDateTime,
+    options: &ArriveByOptions,
+    max_concurrency: usize,
+) -> MeetingPoints {
+    let pairs: Vec<(usize, usize)> = (0..candidates.len())
+        .flat_map(|c| (0..origins.len()).map(move |o| (c, o)))
+        .collect();
+
+    let mut legs: Vec<(usize, usize, MeetingLeg)> = stream::iter(pairs)
+        .map(|(c, o)| async move {
+            let (origin, station) = (&origins[o], &candidates[c]);
+            let leg = if same_station(origin, station) {
+                MeetingLeg::staying(origin)
+            } else {
+                match plan_arrive_by(origin, station, deadline, options).await {
+                    Ok(trip) => MeetingLeg::from_trip(origin, trip.as_ref()),
+                    Err(e) => MeetingLeg::failed(origin, e.to_string()),
+                }
+            };
+            (c, o, leg)
+        })
+        .buffer_unordered(max_concurrency.max(1))
+        .collect()
+        .await;
+    legs.sort_by_key(|(c, o, _)| (*c, *o));
+
+    let mut per_candidate: Vec<Vec<MeetingLeg>> = vec![Vec::new(); candidates.len()];
+    for (c, _, leg) in legs {
+        per_candidate[c].push(leg);
+    }
+
+    let mut points = MeetingPoints {
+        origins: origins.to_vec(),
+        candidates: candidates
+            .iter()
+            .zip(per_candidate)
+            .map(|(station, legs)| MeetingCandidate::new(station.clone(), legs))
+            .collect(),
+    };
+    points.rank();
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+
+    fn leg(origin: &str, minutes: Option<i64>) -> MeetingLeg {
+        MeetingLeg {
+            duration_minutes: minutes,
+            ..MeetingLeg::from_trip(&StationRef::new(origin, None), None)
+        }
+    }
+
+    #[test]
+    fn test_leg_from_trip() {
+        let trips = trips("sbb_api_response_0.json");
+        let origin = StationRef::new("Basel SBB", None);
+        let leg = MeetingLeg::from_trip(&origin, trips.first());
+        assert_eq!(leg.departure.unwrap().to_rfc3339(), "2026-02-22T13:00:00+01:00");
+        assert_eq!(leg.duration_minutes, Some(84));
+
+        let leg = MeetingLeg::from_trip(&origin, None);
+        assert!(leg.duration_minutes.is_none());
+        assert!(leg.error.is_none());
+        assert_eq!(MeetingLeg::staying(&origin).duration_minutes, Some(0));
+
+        assert!(same_station(&"8500010".parse().unwrap(), &StationRef::new("Basel SBB", Some("8500010"))));
+        assert!(same_station(&StationRef::new("bern", None), &StationRef::new("Bern", Some("8507000"))));
+        assert!(!same_station(&"8500010".parse().unwrap(), &"8507000".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_rank() {
+        let mut points = MeetingPoints {
+            origins: vec![StationRef::new("Basel SBB", None), StationRef::new("Bern", None), StationRef::new("Lugano", None)],
+            candidates: vec![
+                MeetingCandidate::new(
+                    StationRef::new("Zürich HB", None),
+                    vec![leg("Basel SBB", Some(53)), leg("Bern", Some(56)), leg("Lugano", Some(116))],
+                ),
+                MeetingCandidate::new(
+                    StationRef::new("Airolo", None),
+                    vec![leg("Basel SBB", Some(150)), leg("Bern", None), leg("Lugano", Some(40))],
+                ),
+                MeetingCandidate::new(
+                    StationRef::new("Luzern", None),
+                    vec![leg("Basel SBB", Some(60)), leg("Bern", Some(60)), leg("Lugano", Some(116))],
+                ),
+                MeetingCandidate::new(
+                    StationRef::new("Arth-Goldau", None),
+                    vec![leg("Basel SBB", Some(80)), leg("Bern", Some(90)), leg("Lugano", Some(90))],
+                ),
+            ],
+        };
+        assert_eq!(points.candidates[0].max_minutes, Some(116));
+        assert_eq!(points.candidates[0].total_minutes, Some(225));
+        assert!(!points.candidates[1].is_reachable());
+
+        points.rank();
+        let order: Vec<&str> = points.candidates.iter().map(|c| c.station.name.as_str()).collect();
+        // Same longest trip as Luzern, but less time in total
+        assert_eq!(order, vec!["Arth-Goldau", "Zürich HB", "Luzern", "Airolo"]);
+
+        let csv = points.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "rank,station,max_minutes,total_minutes,Basel SBB,Bern,Lugano");
+        assert_eq!(lines[1], "1,Arth-Goldau,90,260,80,90,90");
+        assert_eq!(lines[4], "4,Airolo,,,150,,40");
+    }
+}
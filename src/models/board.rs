@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::transport::TransportDesignation;
+use crate::models::trip::{Quay, RtType, StopTime};
+
+/// Response from `GET /api/timetable/v2/stationboard`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StationBoardResponse {
+    /// Name of the station the board is for.
+    pub place_name: Option<String>,
+    #[serde(default)]
+    pub entries: Vec<BoardEntry>,
+}
+
+/// One departure (or arrival) on a station board. Corresponds to `StationBoardEntryDto`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardEntry {
+    pub transport_designation: Option<TransportDesignation>,
+    /// Destination for departures, origin for arrivals.
+    pub direction: Option<String>,
+    /// Scheduled and real-time time at the station.
+    pub time: Option<StopTime>,
+    pub quay: Option<Quay>,
+    /// Real-time event types affecting this ride.
+    #[serde(default)]
+    pub rt_types: Vec<RtType>,
+}
+
+impl BoardEntry {
+    pub fn is_cancelled(&self) -> bool {
+        self.rt_types.iter().any(RtType::is_cancellation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_board() {
+        let board: StationBoardResponse = serde_json::from_str(
+            r#"{
+                "placeName": "Olten",
+                "entries": [
+                    {
+                        "transportDesignation": {"transportDisplayName": "IC 5"},
+                        "direction": "Zürich HB",
+                        "time": {"timeAimed": "2026-02-22T13:56:00+01:00", "timeExpected": "2026-02-22T13:58:00+01:00"},
+                        "quay": {"name": "7", "changed": false}
+                    },
+                    {"direction": "Bern", "rtTypes": ["CANCELLATION"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(board.entries.len(), 2);
+        assert_eq!(board.entries[0].time.as_ref().unwrap().delay_minutes(), Some(2));
+        assert!(!board.entries[0].is_cancelled());
+        assert!(board.entries[1].is_cancelled());
+    }
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::models::trip::RtType;
+
+/// Response from `GET /api/timetable/v2/disruptions`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DisruptionResponse {
+    #[serde(default)]
+    pub messages: Vec<DisruptionMessage>,
+}
+
+/// A network-wide disruption or service message. Corresponds to `DisruptionMessageDto`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DisruptionMessage {
+    pub id: String,
+    pub rt_type: Option<RtType>,
+    pub title: String,
+    /// Full message text, may span several paragraphs.
+    pub text: Option<String>,
+    /// Affected lines, e.g. "IC 5" or "S3".
+    #[serde(default)]
+    pub affected_lines: Vec<String>,
+    #[serde(default)]
+    pub affected_stations: Vec<AffectedStation>,
+    #[serde(default)]
+    pub validity_periods: Vec<ValidityPeriod>,
+}
+
+impl DisruptionMessage {
+    /// Whether the message concerns the station with the given UIC reference.
+    pub fn affects_station(&self, uic: &str) -> bool {
+        self.affected_stations
+            .iter()
+            .any(|s| s.place_reference.as_deref() == Some(uic))
+    }
+
+    /// Whether the message concerns the given line. Comparison ignores case and spaces,
+    /// so "ic5" matches "IC 5".
+    pub fn affects_line(&self, line: &str) -> bool {
+        let wanted = normalize_line(line);
+        self.affected_lines.iter().any(|l| normalize_line(l) == wanted)
+    }
+
+    /// Whether any validity period covers `at`. Messages without periods are always valid.
+    pub fn is_valid_at(&self, at: &DateTime<FixedOffset>) -> bool {
+        self.validity_periods.is_empty() || self.validity_periods.iter().any(|p| p.contains(at))
+    }
+}
+
+fn normalize_line(line: &str) -> String {
+    line.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// A station affected by a disruption.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedStation {
+    pub display_name: String,
+    /// UIC station reference.
+    pub place_reference: Option<String>,
+}
+
+/// Period in which a disruption applies. Open ends are unbounded.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidityPeriod {
+    /// Start of the period (ISO 8601 datetime).
+    pub start: Option<String>,
+    /// End of the period (ISO 8601 datetime).
+    pub end: Option<String>,
+}
+
+impl ValidityPeriod {
+    pub fn contains(&self, at: &DateTime<FixedOffset>) -> bool {
+        let parse = |t: &Option<String>| t.as_deref().and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+        let after_start = parse(&self.start).map(|s| s <= *at).unwrap_or(true);
+        let before_end = parse(&self.end).map(|e| *at <= e).unwrap_or(true);
+        after_start && before_end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> DisruptionResponse {
+        serde_json::from_str(
+            r#"{
+                "messages": [{
+                    "id": "1",
+                    "rtType": "DISRUPTION",
+                    "title": "Olten - Bern: Einschränkungen",
+                    "text": "Wegen einer Fahrleitungsstörung ...",
+                    "affectedLines": ["IC 5", "IR 16"],
+                    "affectedStations": [{"displayName": "Olten", "placeReference": "8500218"}],
+                    "validityPeriods": [{"start": "2026-02-22T10:00:00+01:00", "end": "2026-02-22T14:00:00+01:00"}]
+                }]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_disruption_filters() {
+        let msg = &sample().messages[0];
+        assert_eq!(msg.rt_type, Some(RtType::Disruption));
+        assert!(msg.affects_station("8500218"));
+        assert!(!msg.affects_station("8503000"));
+        assert!(msg.affects_line("ic5"));
+        assert!(!msg.affects_line("IC 1"));
+    }
+
+    #[test]
+    fn test_disruption_validity() {
+        let msg = &sample().messages[0];
+        let during = DateTime::parse_from_rfc3339("2026-02-22T12:00:00+01:00").unwrap();
+        let after = DateTime::parse_from_rfc3339("2026-02-22T15:00:00+01:00").unwrap();
+        assert!(msg.is_valid_at(&during));
+        assert!(!msg.is_valid_at(&after));
+    }
+}
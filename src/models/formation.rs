@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::offer::TravelClass;
+
+/// Response from a leg's `formationPath` or `GET /api/timetable/v2/formations`.
+/// Describes the train composition at the departure stop. Corresponds to `FormationDto`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Formation {
+    /// Stop at which the composition applies.
+    pub stop_place_name: Option<String>,
+    /// Platform sectors in platform order, e.g. ["A", "B", "C", "D"].
+    #[serde(default)]
+    pub sectors: Vec<String>,
+    /// Coaches in platform order, i.e. starting at the first sector.
+    #[serde(default)]
+    pub coaches: Vec<Coach>,
+}
+
+impl Formation {
+    /// The first coach offering `feature`, with its position in `coaches`.
+    pub fn find(&self, feature: CoachFeature) -> Option<(usize, &Coach)> {
+        self.coaches
+            .iter()
+            .enumerate()
+            .find(|(_, c)| c.features.contains(&feature))
+    }
+
+    /// Sectors in which first-class coaches stop.
+    pub fn first_class_sectors(&self) -> Vec<&str> {
+        let mut sectors: Vec<&str> = Vec::new();
+        for coach in self.coaches.iter().filter(|c| c.travel_classes.contains(&TravelClass::First)) {
+            if let Some(s) = coach.sector.as_deref() {
+                if !sectors.contains(&s) {
+                    sectors.push(s);
+                }
+            }
+        }
+        sectors
+    }
+}
+
+/// A single vehicle of a train. Corresponds to `FormationCoachDto`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Coach {
+    /// Coach number as displayed on the vehicle, e.g. "12".
+    pub number: Option<String>,
+    /// Classes offered in the coach. Empty for locomotives and restaurant cars.
+    #[serde(default)]
+    pub travel_classes: Vec<TravelClass>,
+    /// Platform sector the coach stops in.
+    pub sector: Option<String>,
+    #[serde(default)]
+    pub features: Vec<CoachFeature>,
+    /// Coach is closed to passengers.
+    #[serde(default)]
+    pub closed: bool,
+}
+
+/// Service offered in a coach. Corresponds to `CoachFeatureDto`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CoachFeature {
+    Locomotive,
+    Restaurant,
+    Bike,
+    Family,
+    Wheelchair,
+    Sleeper,
+    Quiet,
+    Luggage,
+    /// Any feature not known to this library.
+    #[serde(other)]
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formation() {
+        let json = r#"{
+            "stopPlaceName": "Zürich HB",
+            "sectors": ["A", "B", "C", "D"],
+            "coaches": [
+                {"features": ["LOCOMOTIVE"], "sector": "A"},
+                {"number": "1", "travelClasses": ["FIRST"], "sector": "A", "features": ["QUIET"]},
+                {"number": "2", "travelClasses": ["FIRST", "SECOND"], "sector": "B"},
+                {"number": "3", "sector": "B", "features": ["RESTAURANT"]},
+                {"number": "4", "travelClasses": ["SECOND"], "sector": "C", "features": ["BIKE", "FAMILY", "PANORAMA"]},
+                {"number": "5", "travelClasses": ["SECOND"], "sector": "D", "closed": true}
+            ]
+        }"#;
+        let formation: Formation = serde_json::from_str(json).unwrap();
+        assert_eq!(formation.coaches.len(), 6);
+        assert_eq!(formation.first_class_sectors(), vec!["A", "B"]);
+
+        let (idx, restaurant) = formation.find(CoachFeature::Restaurant).unwrap();
+        assert_eq!(idx, 3);
+        assert_eq!(restaurant.sector.as_deref(), Some("B"));
+
+        let (_, bike) = formation.find(CoachFeature::Bike).unwrap();
+        assert!(bike.features.contains(&CoachFeature::Unknown));
+        assert!(formation.coaches[5].closed);
+    }
+}
@@ -1,7 +1,10 @@
 use core::fmt;
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::str::FromStr;
 
 /// Search date/time interpretation for connection queries.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SearchDateTimeType {
     Departure,
     Arrival,
@@ -15,3 +18,58 @@ impl fmt::Display for SearchDateTimeType {
         }
     }
 }
+
+/// A station given by name, UIC reference, or both.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StationRef {
+    pub name: String,
+    /// UIC station reference (e.g. "8503000" for Zürich HB).
+    pub reference: Option<String>,
+}
+
+impl StationRef {
+    pub fn new(name: &str, reference: Option<&str>) -> Self {
+        StationRef {
+            name: name.to_string(),
+            reference: reference.map(str::to_string),
+        }
+    }
+}
+
+/// Parses a station name, or a UIC reference when the input is all digits.
+impl FromStr for StationRef {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+            Ok(StationRef { name: String::new(), reference: Some(s.to_string()) })
+        } else {
+            Ok(StationRef { name: s.to_string(), reference: None })
+        }
+    }
+}
+
+impl fmt::Display for StationRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.reference, self.name.is_empty()) {
+            (Some(r), true) => write!(f, "{}", r),
+            _ => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_station_ref() {
+        let by_ref: StationRef = "8503000".parse().unwrap();
+        assert_eq!(by_ref.reference.as_deref(), Some("8503000"));
+        assert_eq!(by_ref.to_string(), "8503000");
+
+        let by_name: StationRef = " Zürich HB ".parse().unwrap();
+        assert_eq!(by_name, StationRef::new("Zürich HB", None));
+    }
+}
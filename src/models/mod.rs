@@ -2,3 +2,7 @@ pub mod trip;
 pub mod transport;
 pub mod place;
 pub mod location;
+pub mod formation;
+pub mod disruption;
+pub mod offer;
+pub mod board;
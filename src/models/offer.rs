@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Response from `GET /api/timetable/v2/trips/{tripId}/prices`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OfferResponse {
+    #[serde(default)]
+    pub offers: Vec<Offer>,
+}
+
+impl OfferResponse {
+    /// The cheapest offer that has a price, if any.
+    pub fn cheapest(&self) -> Option<&Offer> {
+        self.offers
+            .iter()
+            .filter(|o| o.price.is_some())
+            .min_by_key(|o| o.price.as_ref().map(|p| p.amount_in_cents))
+    }
+}
+
+/// A purchasable ticket offer for a trip. Corresponds to `OfferDto`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Offer {
+    /// Offer title, e.g. "Sparbillett" or "Einzelbillett".
+    pub title: Option<String>,
+    pub travel_class: Option<TravelClass>,
+    pub price: Option<Price>,
+    /// Whether the offer is a discounted, train-bound saver ticket.
+    #[serde(default)]
+    pub is_supersaver: bool,
+}
+
+/// A price in the smallest currency unit. Corresponds to `PriceDto`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Price {
+    pub amount_in_cents: u32,
+    /// ISO 4217 currency code, e.g. "CHF".
+    pub currency: String,
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}.{:02}",
+            self.currency,
+            self.amount_in_cents / 100,
+            self.amount_in_cents % 100
+        )
+    }
+}
+
+/// Travel class for offers.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TravelClass {
+    First,
+    #[default]
+    Second,
+}
+
+impl fmt::Display for TravelClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TravelClass::First => write!(f, "FIRST"),
+            TravelClass::Second => write!(f, "SECOND"),
+        }
+    }
+}
+
+/// Travelcard held by the traveller, which determines the applicable fare.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reduction {
+    #[default]
+    #[serde(rename = "NONE")]
+    None,
+    /// Half-fare travelcard (Halbtax).
+    #[serde(rename = "HALF_FARE")]
+    HalfFare,
+    /// General travelcard (GA). Most offers are free of charge with it.
+    #[serde(rename = "GA")]
+    GeneralAbonnement,
+}
+
+impl fmt::Display for Reduction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reduction::None => write!(f, "NONE"),
+            Reduction::HalfFare => write!(f, "HALF_FARE"),
+            Reduction::GeneralAbonnement => write!(f, "GA"),
+        }
+    }
+}
+
+/// Traveller options used to price a trip.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TravellerOptions {
+    pub travel_class: TravelClass,
+    pub reduction: Reduction,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_offers() {
+        let json = r#"{
+            "offers": [
+                {"title": "Einzelbillett", "travelClass": "SECOND", "price": {"amountInCents": 2560, "currency": "CHF"}},
+                {"title": "Sparbillett", "travelClass": "SECOND", "price": {"amountInCents": 1240, "currency": "CHF"}, "isSupersaver": true},
+                {"title": "Gruppenbillett"}
+            ]
+        }"#;
+        let resp: OfferResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.offers.len(), 3);
+
+        let cheapest = resp.cheapest().unwrap();
+        assert_eq!(cheapest.title.as_deref(), Some("Sparbillett"));
+        assert!(cheapest.is_supersaver);
+        assert_eq!(cheapest.price.as_ref().unwrap().to_string(), "CHF 12.40");
+    }
+
+    #[test]
+    fn test_cheapest_without_prices() {
+        assert!(OfferResponse::default().cheapest().is_none());
+    }
+}
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use simple_error::SimpleError;
+use std::str::FromStr;
 
 /// Response from `GET /api/timetable/v2/places`.
 /// The API returns a JSON array of places.
@@ -17,8 +19,82 @@ pub struct Place {
     pub coordinates: Coordinates,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl Place {
+    /// Whether the place is a public-transport stop (as opposed to an address or POI).
+    pub fn is_stop(&self) -> bool {
+        self.place_type == "STOP_PLACE"
+    }
+}
+
+/// A place together with its distance from a reference point.
+#[derive(Serialize, Debug, Clone)]
+pub struct NearbyPlace {
+    pub place: Place,
+    /// Great-circle distance in meters.
+    pub distance_m: f64,
+}
+
+/// WGS84 coordinates in decimal degrees.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub struct Coordinates {
     pub latitude: f64,
     pub longitude: f64,
 }
+
+impl Coordinates {
+    const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+    /// Great-circle (haversine) distance to `other` in meters.
+    pub fn distance_to(&self, other: &Coordinates) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (other.longitude - self.longitude).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * Self::EARTH_RADIUS_M * a.sqrt().asin()
+    }
+}
+
+/// Parses `"47.378,8.540"` (latitude first), optionally with spaces after the comma.
+impl FromStr for Coordinates {
+    type Err = SimpleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (lat, lon) = s
+            .split_once(',')
+            .ok_or_else(|| SimpleError::new(format!("expected LAT,LON but got '{}'", s)))?;
+        let parse = |v: &str| {
+            v.trim()
+                .parse::<f64>()
+                .map_err(|_| SimpleError::new(format!("invalid coordinate '{}'", v.trim())))
+        };
+        let (latitude, longitude) = (parse(lat)?, parse(lon)?);
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(SimpleError::new(format!("coordinates out of range: {}", s)));
+        }
+        Ok(Coordinates { latitude, longitude })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_coordinates() {
+        let c: Coordinates = "47.378, 8.540".parse().unwrap();
+        assert_eq!(c, Coordinates { latitude: 47.378, longitude: 8.540 });
+        assert!("47.378".parse::<Coordinates>().is_err());
+        assert!("abc,8.5".parse::<Coordinates>().is_err());
+        assert!("91,8.5".parse::<Coordinates>().is_err());
+    }
+
+    #[test]
+    fn test_distance() {
+        let zurich = Coordinates { latitude: 47.378177, longitude: 8.540193 };
+        let basel = Coordinates { latitude: 47.547408, longitude: 7.589548 };
+        let d = zurich.distance_to(&basel);
+        // ~74 km as the crow flies
+        assert!((73_000.0..76_000.0).contains(&d), "distance was {}", d);
+        assert_eq!(zurich.distance_to(&zurich), 0.0);
+    }
+}
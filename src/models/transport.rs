@@ -11,6 +11,28 @@ pub struct TransportDesignation {
     pub transport_extra_info: Option<String>,
 }
 
+impl TransportDesignation {
+    /// Product category, i.e. the leading letters of the display name: "IC 5" → "IC", "S3" → "S".
+    pub fn category(&self) -> String {
+        self.transport_display_name
+            .trim()
+            .chars()
+            .take_while(|c| c.is_alphabetic())
+            .flat_map(char::to_uppercase)
+            .collect()
+    }
+
+    /// Vehicle mode derived from the icon: "train-right" → "train", "bus-right" → "bus".
+    pub fn vehicle_mode(&self) -> Option<&str> {
+        let icon = self.vehicle_icon.as_deref()?;
+        Some(
+            icon.strip_suffix("-right")
+                .or_else(|| icon.strip_suffix("-left"))
+                .unwrap_or(icon),
+        )
+    }
+}
+
 impl fmt::Display for TransportDesignation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.transport_extra_info {
@@ -21,3 +43,27 @@ impl fmt::Display for TransportDesignation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn designation(name: &str, icon: Option<&str>) -> TransportDesignation {
+        TransportDesignation {
+            vehicle_icon: icon.map(str::to_string),
+            transport_insignia_icon: None,
+            transport_display_name: name.to_string(),
+            transport_extra_info: None,
+        }
+    }
+
+    #[test]
+    fn test_category_and_mode() {
+        assert_eq!(designation("IC 5", Some("train-right")).category(), "IC");
+        assert_eq!(designation("S3", None).category(), "S");
+        assert_eq!(designation("ICE", None).category(), "ICE");
+        assert_eq!(designation("IC 5", Some("train-right")).vehicle_mode(), Some("train"));
+        assert_eq!(designation("B 31", Some("bus")).vehicle_mode(), Some("bus"));
+        assert_eq!(designation("B 31", None).vehicle_mode(), None);
+    }
+}
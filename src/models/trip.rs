@@ -1,9 +1,13 @@
+use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use crate::models::place::Coordinates;
 use crate::models::transport::TransportDesignation;
 
 /// Top-level response from `GET /api/timetable/v2/trips`.
 /// Corresponds to `TripSearchResponseDto` in the Android app.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct TripSearchResponse {
     pub trips: Vec<Trip>,
@@ -13,13 +17,51 @@ pub struct TripSearchResponse {
     pub later_paging_cursor: Option<String>,
 }
 
+/// Which way to page through search results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageDirection {
+    Earlier,
+    Later,
+}
+
+impl TripSearchResponse {
+    /// Cursor to the next page in `direction`, if there is one.
+    pub fn paging_cursor(&self, direction: PageDirection) -> Option<&str> {
+        match direction {
+            PageDirection::Earlier => self.earlier_paging_cursor.as_deref(),
+            PageDirection::Later => self.later_paging_cursor.as_deref(),
+        }
+    }
+
+    /// Add the trips of an earlier or later page, skipping those already present (pages overlap),
+    /// and take over its cursor in that direction. Returns the number of trips added.
+    pub fn merge_page(&mut self, page: TripSearchResponse, direction: PageDirection) -> usize {
+        let known: HashSet<String> = self.trips.iter().map(|t| t.meta.id.clone()).collect();
+        let new: Vec<Trip> = page.trips.into_iter().filter(|t| !known.contains(&t.meta.id)).collect();
+        let added = new.len();
+        match direction {
+            PageDirection::Earlier => {
+                self.trips.splice(0..0, new);
+                self.earlier_paging_cursor = page.earlier_paging_cursor;
+            }
+            PageDirection::Later => {
+                self.trips.extend(new);
+                self.later_paging_cursor = page.later_paging_cursor;
+            }
+        }
+        added
+    }
+}
+
 /// A single connection result. Corresponds to `TripDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Trip {
     pub meta: TripMeta,
     pub summary: TripSummary,
     /// Leg-by-leg detail. Present in list responses; may be absent for some trips.
     pub detail: Option<TripDetail>,
+    /// Journey companion cards, which carry per-leg stop coordinates.
+    pub companion: Option<TripCompanion>,
 }
 
 impl Trip {
@@ -32,18 +74,273 @@ impl Trip {
             .unwrap_or(1);
         pt_legs.saturating_sub(1)
     }
+
+    /// Real-time departure time, falling back to the scheduled time.
+    pub fn departure_time(&self) -> Option<DateTime<FixedOffset>> {
+        let anchor = &self.summary.departure_anchor;
+        parse_time(&anchor.time_expected).or_else(|| parse_time(&anchor.time_aimed))
+    }
+
+    /// Real-time arrival time, falling back to the scheduled time.
+    pub fn arrival_time(&self) -> Option<DateTime<FixedOffset>> {
+        let anchor = &self.summary.arrival_anchor;
+        parse_time(&anchor.time_expected).or_else(|| parse_time(&anchor.time_aimed))
+    }
+
+    /// Travel time in minutes, from the summary or else from the real-time anchors.
+    pub fn duration_minutes(&self) -> Option<i64> {
+        if let Some(d) = &self.summary.duration {
+            return Some(d.duration_in_minutes as i64);
+        }
+        Some((self.arrival_time()? - self.departure_time()?).num_minutes())
+    }
+
+    /// Public-transport ride legs in travel order.
+    pub fn ride_legs(&self) -> impl Iterator<Item = &PtRideLeg> {
+        self.detail
+            .iter()
+            .flat_map(|d| d.legs.iter())
+            .filter_map(|l| match l {
+                TripLeg::PtRideLeg(pt) => Some(pt),
+                _ => None,
+            })
+    }
+
+    /// Time available at each change between consecutive ride legs, in travel order.
+    pub fn change_times(&self) -> Vec<ChangeTime> {
+        let rides: Vec<&PtRideLeg> = self.ride_legs().collect();
+        rides
+            .windows(2)
+            .map(|pair| {
+                let (arriving, departing) = (pair[0], pair[1]);
+                let arrival = arriving.arrival_stop_point.arrival_time.as_ref().and_then(StopTime::time);
+                let departure = departing.departure_stop_point.departure_time.as_ref().and_then(StopTime::time);
+                ChangeTime {
+                    station: departing.departure_stop_point.display_name.clone(),
+                    minutes: match (arrival, departure) {
+                        (Some(a), Some(d)) => Some((d - a).num_minutes()),
+                        _ => None,
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// Total walking time in minutes: the access legs plus the changes made on foot, which
+    /// count with the time between the surrounding rides (0 when unknown).
+    pub fn walking_minutes(&self) -> i64 {
+        let Some(detail) = &self.detail else {
+            return 0;
+        };
+        detail
+            .legs
+            .iter()
+            .enumerate()
+            .map(|(i, leg)| match leg {
+                TripLeg::AccessLeg(a) => a.duration.as_ref().map(|d| d.duration_in_minutes as i64).unwrap_or(0),
+                TripLeg::ChangeLeg(c) if c.is_walk() => {
+                    let arrival = i
+                        .checked_sub(1)
+                        .and_then(|p| ride_time(detail.legs.get(p), |r| &r.arrival_stop_point.arrival_time));
+                    let departure = ride_time(detail.legs.get(i + 1), |r| &r.departure_stop_point.departure_time);
+                    match (arrival, departure) {
+                        (Some(a), Some(d)) => (d - a).num_minutes().max(0),
+                        _ => 0,
+                    }
+                }
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Whether the trip, or any of its ride legs, has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.disruptions().iter().any(|d| d.rt_type.is_cancellation())
+    }
+
+    /// Whether any ride leg departs from or arrives at a changed platform.
+    pub fn has_platform_change(&self) -> bool {
+        let anchors_changed = [&self.summary.departure_anchor.quay, &self.summary.arrival_anchor.quay]
+            .iter()
+            .any(|q| q.as_ref().map(|q| q.changed).unwrap_or(false));
+        anchors_changed
+            || self
+                .disruptions()
+                .iter()
+                .any(|d| d.rt_type == RtType::PlatformChange)
+    }
+
+    /// All real-time events attached to this trip, flattened from the summary and its legs.
+    ///
+    /// Events carried by a leg have `leg_index` set to the leg's position in `TripDetail::legs`.
+    pub fn disruptions(&self) -> Vec<Disruption> {
+        let mut out: Vec<Disruption> = Vec::new();
+        fn push(out: &mut Vec<Disruption>, d: Disruption) {
+            if !out.contains(&d) {
+                out.push(d);
+            }
+        }
+
+        if let Some(detail) = &self.detail {
+            if let Some(d) = detail.rt_pt_ride_leg_info.as_ref().and_then(|i| i.to_disruption(None)) {
+                push(&mut out, d);
+            }
+
+            for (idx, leg) in detail.legs.iter().enumerate() {
+                let pt = match leg {
+                    TripLeg::PtRideLeg(pt) => pt,
+                    _ => continue,
+                };
+
+                for info in &pt.rt_action_infos {
+                    if let Some(rt_type) = &info.rt_type {
+                        push(&mut out, Disruption {
+                            leg_index: Some(idx),
+                            rt_type: rt_type.clone(),
+                            summary: info.summary.clone(),
+                        });
+                    }
+                }
+                // Bare types only add an event when no action info already describes them
+                for rt_type in &pt.rt_types {
+                    if !out.iter().any(|d| d.leg_index == Some(idx) && &d.rt_type == rt_type) {
+                        push(&mut out, Disruption { leg_index: Some(idx), rt_type: rt_type.clone(), summary: None });
+                    }
+                }
+
+                let infos = [
+                    &pt.rt_pt_ride_leg_info,
+                    &pt.departure_stop_point.rt_stop_info,
+                    &pt.arrival_stop_point.rt_stop_info,
+                ];
+                for info in infos.into_iter().flatten() {
+                    if let Some(d) = info.to_disruption(Some(idx)) {
+                        push(&mut out, d);
+                    }
+                }
+
+                for stop in [&pt.departure_stop_point, &pt.arrival_stop_point] {
+                    if let Some(q) = stop.quay.as_ref().filter(|q| q.changed) {
+                        push(&mut out, Disruption {
+                            leg_index: Some(idx),
+                            rt_type: RtType::PlatformChange,
+                            summary: Some(format!("{}: {}", stop.display_name, q.name)),
+                        });
+                    }
+                }
+            }
+        }
+
+        // The summary only carries the most relevant type; keep it when the legs said nothing about it
+        if let Some(rt_type) = &self.summary.most_relevant_rt_type {
+            if !out.iter().any(|d| &d.rt_type == rt_type) {
+                push(&mut out, Disruption { leg_index: None, rt_type: rt_type.clone(), summary: None });
+            }
+        }
+
+        out
+    }
+
+    /// Every stop served by a leg, first and last included, from the journey companion.
+    /// Empty when the API sent no companion card for the leg.
+    pub fn leg_stops(&self, leg_index: usize) -> &[StopMetadata] {
+        let id = match self.detail.as_ref().and_then(|d| d.legs.get(leg_index)) {
+            Some(leg) => leg.rokas_leg_identifier(),
+            None => return &[],
+        };
+        id.and_then(|id| {
+            self.companion
+                .as_ref()?
+                .cards
+                .iter()
+                .find(|c| c.rokas_leg_identifier.as_deref() == Some(id))
+        })
+        .map(|c| c.stops_metadata.as_slice())
+        .unwrap_or_default()
+    }
+
+    /// Coordinates describing the path of the leg at `leg_index` in `TripDetail::legs`.
+    ///
+    /// Uses the leg polyline when the API provides one, then the stops of the matching
+    /// companion card, and finally the coordinates of the leg's own stop points.
+    pub fn leg_geometry(&self, leg_index: usize) -> Vec<Coordinates> {
+        let leg = match self.detail.as_ref().and_then(|d| d.legs.get(leg_index)) {
+            Some(leg) => leg,
+            None => return Vec::new(),
+        };
+
+        if let Some(polyline) = leg.polyline().filter(|p| !p.is_empty()) {
+            return polyline.to_vec();
+        }
+
+        let stops = self.leg_stops(leg_index);
+        if stops.len() >= 2 {
+            return stops.iter().filter_map(|s| s.coordinates).collect();
+        }
+
+        let (dep, arr) = leg.stop_points();
+        [dep, arr]
+            .into_iter()
+            .flatten()
+            .filter_map(|s| s.coordinates)
+            .collect()
+    }
+}
+
+/// Parse an ISO 8601 datetime as returned by the API.
+pub(crate) fn parse_time(time: &Option<String>) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(time.as_deref()?).ok()
+}
+
+fn delay_minutes(aimed: &Option<String>, expected: &Option<String>) -> Option<i64> {
+    Some((parse_time(expected)? - parse_time(aimed)?).num_minutes())
+}
+
+fn quay_name(quay: &Option<Quay>) -> Option<&str> {
+    quay.as_ref().map(|q| q.name.as_str()).filter(|n| !n.is_empty())
+}
+
+/// A change between two ride legs, as returned by [`Trip::change_times`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeTime {
+    /// Station where the next ride departs.
+    pub station: String,
+    /// Minutes between arrival and the next departure, `None` when times are missing.
+    pub minutes: Option<i64>,
+}
+
+/// A single real-time event on a trip, as returned by [`Trip::disruptions`].
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Disruption {
+    /// Index into `TripDetail::legs`, or `None` for trip-level events.
+    pub leg_index: Option<usize>,
+    pub rt_type: RtType,
+    /// Short human-readable description, when the API provides one.
+    pub summary: Option<String>,
+}
+
+/// "Delay: +5 min" or just "Delay" without a summary.
+impl fmt::Display for Disruption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.summary {
+            Some(summary) => write!(f, "{}: {}", self.rt_type, summary),
+            None => write!(f, "{}", self.rt_type),
+        }
+    }
 }
 
 /// Metadata for a trip. Corresponds to `TripMetaDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TripMeta {
     pub id: String,
     pub next_refresh: Option<i64>,
+    /// Identifier of the route geometry on SBB's map backend.
+    pub geo_route_id: Option<String>,
 }
 
 /// Summary card data shown in the connection list. Corresponds to `TripSummaryDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TripSummary {
     pub duration: Option<TripDuration>,
@@ -51,6 +348,8 @@ pub struct TripSummary {
     pub occupancy_first_class_max: Option<String>,
     /// Occupancy level for second class.
     pub occupancy_second_class_max: Option<String>,
+    /// Most relevant real-time event type across all legs.
+    pub most_relevant_rt_type: Option<RtType>,
     pub departure_display_name: String,
     pub arrival_display_name: String,
     pub departure_anchor: DepartureAnchor,
@@ -58,7 +357,7 @@ pub struct TripSummary {
 }
 
 /// Human-readable duration. Corresponds to `DurationDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TripDuration {
     /// Localized label, e.g. "56 min" or "1 h 05 min".
@@ -67,10 +366,13 @@ pub struct TripDuration {
 }
 
 /// Departure end of the connection summary. Corresponds to `TripSummaryDepartureAnchorDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DepartureAnchor {
     pub place_name: String,
+    /// UIC station reference of the departure place.
+    pub place_reference: Option<String>,
+    pub place_coordinates: Option<Coordinates>,
     /// Scheduled departure time (ISO 8601 local datetime string).
     pub time_aimed: Option<String>,
     /// Expected (real-time) departure time.
@@ -84,11 +386,25 @@ pub struct DepartureAnchor {
     pub transport_designation: Option<TransportDesignation>,
 }
 
+impl DepartureAnchor {
+    /// Departure delay in minutes (negative when early), `None` without both times.
+    pub fn delay_minutes(&self) -> Option<i64> {
+        delay_minutes(&self.time_aimed, &self.time_expected)
+    }
+
+    /// Platform as named by the API, e.g. "Gl. 7"; `None` when unknown.
+    pub fn quay_name(&self) -> Option<&str> {
+        quay_name(&self.quay)
+    }
+}
+
 /// Arrival end of the connection summary. Corresponds to `TripSummaryArrivalAnchorDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ArrivalAnchor {
     pub place_name: String,
+    pub place_reference: Option<String>,
+    pub place_coordinates: Option<Coordinates>,
     pub time_aimed: Option<String>,
     pub time_expected: Option<String>,
     pub display_time: String,
@@ -96,25 +412,46 @@ pub struct ArrivalAnchor {
     pub quay: Option<Quay>,
 }
 
+impl ArrivalAnchor {
+    /// Arrival delay in minutes (negative when early), `None` without both times.
+    pub fn delay_minutes(&self) -> Option<i64> {
+        delay_minutes(&self.time_aimed, &self.time_expected)
+    }
+
+    /// Platform as named by the API, e.g. "Gl. 7"; `None` when unknown.
+    pub fn quay_name(&self) -> Option<&str> {
+        quay_name(&self.quay)
+    }
+}
+
 /// Platform / track information. Corresponds to `QuayDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Quay {
     pub name: String,
     pub changed: bool,
 }
 
 /// Leg-level detail for a trip. Corresponds to `TripDetailListDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TripDetail {
     pub legs: Vec<TripLeg>,
     pub rt_pt_ride_leg_info: Option<RtInfo>,
 }
 
+/// Time at a ride leg's stop, as picked by `stop`; `None` unless `leg` is a ride with a known time.
+fn ride_time(leg: Option<&TripLeg>, stop: fn(&PtRideLeg) -> &Option<StopTime>) -> Option<DateTime<FixedOffset>> {
+    match leg {
+        Some(TripLeg::PtRideLeg(ride)) => stop(ride).as_ref().and_then(StopTime::time),
+        _ => None,
+    }
+}
+
 /// A polymorphic leg within a trip. The `type` field selects the variant.
 /// Corresponds to the sealed `TripLegDto` hierarchy in the Android app.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
+#[allow(clippy::large_enum_variant)] // plain DTO, kept unboxed so variants can be matched directly
 pub enum TripLeg {
     /// Public-transport ride leg (type = "PtRideLeg").
     PtRideLeg(PtRideLeg),
@@ -124,39 +461,97 @@ pub enum TripLeg {
     ChangeLeg(ChangeLeg),
 }
 
+impl TripLeg {
+    /// Identifier linking this leg to its companion card (e.g. "0", "0-1").
+    pub fn rokas_leg_identifier(&self) -> Option<&str> {
+        match self {
+            TripLeg::PtRideLeg(l) => l.rokas_leg_identifier.as_deref(),
+            TripLeg::AccessLeg(l) => l.rokas_leg_identifier.as_deref(),
+            TripLeg::ChangeLeg(l) => l.rokas_leg_identifier.as_deref(),
+        }
+    }
+
+    /// Departure and arrival stop points of the leg, when known.
+    pub fn stop_points(&self) -> (Option<&StopPoint>, Option<&StopPoint>) {
+        match self {
+            TripLeg::PtRideLeg(l) => (Some(&l.departure_stop_point), Some(&l.arrival_stop_point)),
+            TripLeg::AccessLeg(l) => (l.departure_stop_point.as_ref(), l.arrival_stop_point.as_ref()),
+            TripLeg::ChangeLeg(l) => (l.departure_stop_point.as_ref(), l.arrival_stop_point.as_ref()),
+        }
+    }
+
+    /// Path of the leg as provided by the API, if any.
+    pub fn polyline(&self) -> Option<&[Coordinates]> {
+        match self {
+            TripLeg::PtRideLeg(l) => l.polyline.as_deref(),
+            TripLeg::AccessLeg(l) => l.polyline.as_deref(),
+            TripLeg::ChangeLeg(_) => None,
+        }
+    }
+}
+
 /// A public-transport ride segment. Corresponds to `TripLegDto$PtRideLeg`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PtRideLeg {
+    pub rokas_leg_identifier: Option<String>,
     pub direction: Option<String>,
     pub marketing_name: Option<String>,
     pub first_transport_designation: Option<TransportDesignation>,
     pub departure_stop_point: StopPoint,
     pub arrival_stop_point: StopPoint,
     pub rt_pt_ride_leg_info: Option<RtInfo>,
+    /// Real-time event types affecting this ride.
+    #[serde(default)]
+    pub rt_types: Vec<RtType>,
+    /// Real-time events with their user-facing texts.
+    #[serde(default)]
+    pub rt_action_infos: Vec<RtActionInfo>,
+    /// Path of the ride, when the API provides it.
+    pub polyline: Option<Vec<Coordinates>>,
+    /// API path of the train composition, see [`get_leg_formation`](crate::formation::get_leg_formation).
+    pub formation_path: Option<String>,
 }
 
 /// A walking or access segment.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AccessLeg {
+    pub rokas_leg_identifier: Option<String>,
+    /// Walking time.
+    pub duration: Option<TripDuration>,
     pub departure_stop_point: Option<StopPoint>,
     pub arrival_stop_point: Option<StopPoint>,
+    /// Walking path, when the API provides it.
+    pub polyline: Option<Vec<Coordinates>>,
 }
 
 /// A transfer / change segment between two PT rides.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ChangeLeg {
+    pub rokas_leg_identifier: Option<String>,
+    /// How the change is made, e.g. "WALK".
+    pub access_mode: Option<String>,
     pub departure_stop_point: Option<StopPoint>,
     pub arrival_stop_point: Option<StopPoint>,
 }
 
+impl ChangeLeg {
+    /// Whether the change is made on foot.
+    pub fn is_walk(&self) -> bool {
+        self.access_mode.as_deref() == Some("WALK")
+    }
+}
+
 /// A stop within a leg. Corresponds to `ScheduledStopPointDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StopPoint {
     pub display_name: String,
+    /// UIC station reference of the stop.
+    pub place_reference: Option<String>,
+    pub coordinates: Option<Coordinates>,
     pub occupancy_first_class: Option<String>,
     pub occupancy_second_class: Option<String>,
     pub arrival_time: Option<StopTime>,
@@ -166,7 +561,7 @@ pub struct StopPoint {
 }
 
 /// Scheduled and real-time times at a stop. Corresponds to `StopTimeDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StopTime {
     /// Scheduled time (ISO 8601 local datetime).
@@ -177,15 +572,159 @@ pub struct StopTime {
     pub display_time: Option<String>,
 }
 
+/// Journey companion data attached to a trip. Corresponds to `JourneyCompanionDto`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TripCompanion {
+    #[serde(default)]
+    pub cards: Vec<CompanionCard>,
+}
+
+/// A companion card for a single leg. Only the location-related fields are modelled.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanionCard {
+    /// Identifier of the leg this card belongs to (matches `PtRideLeg::rokas_leg_identifier`).
+    pub rokas_leg_identifier: Option<String>,
+    /// Every stop served along the leg, including intermediate ones.
+    #[serde(default)]
+    pub stops_metadata: Vec<StopMetadata>,
+}
+
+/// Location and timing of a stop on a companion card. Corresponds to `StopMetadataDto`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StopMetadata {
+    pub place_reference: Option<String>,
+    pub coordinates: Option<Coordinates>,
+    pub arrival_date_time: Option<String>,
+    pub departure_date_time: Option<String>,
+}
+
+impl StopPoint {
+    /// Platform as named by the API, e.g. "Gl. 7"; `None` when unknown.
+    pub fn quay_name(&self) -> Option<&str> {
+        quay_name(&self.quay)
+    }
+}
+
+impl StopTime {
+    /// Delay in minutes (negative when early), `None` without both times.
+    pub fn delay_minutes(&self) -> Option<i64> {
+        delay_minutes(&self.time_aimed, &self.time_expected)
+    }
+
+    /// Real-time time, falling back to the scheduled time.
+    pub fn time(&self) -> Option<DateTime<FixedOffset>> {
+        parse_time(&self.time_expected).or_else(|| parse_time(&self.time_aimed))
+    }
+}
+
 /// Real-time disruption info. Corresponds to `RtInfoDto`.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RtInfo {
-    /// Type of real-time event: "DELAY", "CANCELLED", "PLATFORM_CHANGE", etc.
-    pub rt_type: Option<String>,
+    /// Type of real-time event.
+    pub rt_type: Option<RtType>,
     pub display_name: Option<String>,
 }
 
+impl RtInfo {
+    fn to_disruption(&self, leg_index: Option<usize>) -> Option<Disruption> {
+        Some(Disruption {
+            leg_index,
+            rt_type: self.rt_type.clone()?,
+            summary: self.display_name.clone(),
+        })
+    }
+}
+
+/// A real-time event with its texts. Corresponds to `RtActionInfoDto`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RtActionInfo {
+    pub rt_type: Option<RtType>,
+    /// One-line summary, e.g. "Änderungen in der Zugskomposition".
+    pub summary: Option<String>,
+    pub dialog_title: Option<String>,
+}
+
+/// Type of a real-time event. Corresponds to `RtTypeDto`.
+///
+/// Every variant serializes back to the token it was read from, so `--json` output
+/// matches the API.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RtType {
+    Delay,
+    Cancelled,
+    /// Spelling of [`Cancelled`](RtType::Cancelled) used by some endpoints.
+    Cancellation,
+    PartiallyCancelled,
+    /// Spelling of [`PartiallyCancelled`](RtType::PartiallyCancelled) used by some endpoints.
+    PartialCancellation,
+    PlatformChange,
+    Disruption,
+    Replacement,
+    Information,
+    /// Any type not known to this library, as received.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl RtType {
+    /// Whether the event means (part of) the ride will not run.
+    pub fn is_cancellation(&self) -> bool {
+        matches!(
+            self,
+            RtType::Cancelled | RtType::Cancellation | RtType::PartiallyCancelled | RtType::PartialCancellation
+        )
+    }
+
+    /// Whether the event affects the journey, as opposed to being purely informational.
+    pub fn is_disruptive(&self) -> bool {
+        !matches!(self, RtType::Information | RtType::Unknown(_))
+    }
+}
+
+impl fmt::Display for RtType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            RtType::Delay => "Delay",
+            RtType::Cancelled | RtType::Cancellation => "Cancelled",
+            RtType::PartiallyCancelled | RtType::PartialCancellation => "Partially cancelled",
+            RtType::PlatformChange => "Platform change",
+            RtType::Disruption => "Disruption",
+            RtType::Replacement => "Replacement service",
+            RtType::Information => "Information",
+            RtType::Unknown(_) => "Notice",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Recorded API responses in `resources/test`, shared by the tests of the crate.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::{Trip, TripSearchResponse};
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    /// The recorded response in `file`, e.g. "sbb_api_response_0.json" (Basel SBB → Bern).
+    pub(crate) fn response(file: &str) -> TripSearchResponse {
+        let data = std::fs::read(format!("./resources/test/{}", file)).unwrap();
+        serde_json::from_slice(&data).unwrap()
+    }
+
+    /// Trips of the recorded response in `file`.
+    pub(crate) fn trips(file: &str) -> Vec<Trip> {
+        response(file).trips
+    }
+
+    /// Local time on 2026-02-22, the day the responses were recorded.
+    pub(crate) fn at(h: u32, m: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 2, 22).unwrap().and_hms_opt(h, m, 0).unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,8 +732,7 @@ mod tests {
     #[test]
     fn test_parse_bern_basel() {
         // This test file is Basel SBB -> Bern (arrival search at 15:00)
-        let data = std::fs::read("./resources/test/sbb_api_response_0.json").unwrap();
-        let resp: TripSearchResponse = serde_json::from_slice(&data).unwrap();
+        let resp = fixtures::response("sbb_api_response_0.json");
         assert!(!resp.trips.is_empty());
 
         let trip = &resp.trips[0];
@@ -229,8 +767,7 @@ mod tests {
 
     #[test]
     fn test_parse_basel_bern_arrival() {
-        let data = std::fs::read("./resources/test/sbb_api_response_1.json").unwrap();
-        let resp: TripSearchResponse = serde_json::from_slice(&data).unwrap();
+        let resp = fixtures::response("sbb_api_response_1.json");
         assert!(!resp.trips.is_empty());
 
         let trip = &resp.trips[0];
@@ -240,8 +777,7 @@ mod tests {
 
     #[test]
     fn test_parse_geneve_zurich() {
-        let data = std::fs::read("./resources/test/sbb_api_response_2.json").unwrap();
-        let resp: TripSearchResponse = serde_json::from_slice(&data).unwrap();
+        let resp = fixtures::response("sbb_api_response_2.json");
         assert!(!resp.trips.is_empty());
 
         let trip = &resp.trips[0];
@@ -256,8 +792,7 @@ mod tests {
 
     #[test]
     fn test_parse_zurich_chur() {
-        let data = std::fs::read("./resources/test/sbb_api_response_3.json").unwrap();
-        let resp: TripSearchResponse = serde_json::from_slice(&data).unwrap();
+        let resp = fixtures::response("sbb_api_response_3.json");
         assert!(!resp.trips.is_empty());
 
         let trip = &resp.trips[0];
@@ -273,8 +808,7 @@ mod tests {
 
     #[test]
     fn test_occupancy_parsing() {
-        let data = std::fs::read("./resources/test/sbb_api_response_0.json").unwrap();
-        let resp: TripSearchResponse = serde_json::from_slice(&data).unwrap();
+        let resp = fixtures::response("sbb_api_response_0.json");
 
         let trip = &resp.trips[0];
         assert_eq!(trip.summary.occupancy_first_class_max.as_deref(), Some("LOW"));
@@ -283,10 +817,150 @@ mod tests {
 
     #[test]
     fn test_paging_cursors() {
-        let data = std::fs::read("./resources/test/sbb_api_response_0.json").unwrap();
-        let resp: TripSearchResponse = serde_json::from_slice(&data).unwrap();
+        let resp = fixtures::response("sbb_api_response_0.json");
 
         assert!(resp.earlier_paging_cursor.is_some());
         assert!(resp.later_paging_cursor.is_some());
     }
+
+    #[test]
+    fn test_merge_page() {
+        let full = fixtures::response("sbb_api_response_0.json");
+        let n = full.trips.len();
+
+        let mut resp = full.clone();
+        resp.trips = full.trips[1..3].to_vec();
+        resp.later_paging_cursor = None;
+
+        // Overlapping pages only add the trips not yet present
+        let mut later = full.clone();
+        later.trips = full.trips[2..].to_vec();
+        assert_eq!(resp.merge_page(later, PageDirection::Later), n - 3);
+        assert_eq!(resp.paging_cursor(PageDirection::Later), full.later_paging_cursor.as_deref());
+
+        let mut earlier = full.clone();
+        earlier.trips = full.trips[..2].to_vec();
+        earlier.earlier_paging_cursor = None;
+        assert_eq!(resp.merge_page(earlier, PageDirection::Earlier), 1);
+        assert_eq!(resp.paging_cursor(PageDirection::Earlier), None);
+
+        let ids: Vec<&str> = resp.trips.iter().map(|t| t.meta.id.as_str()).collect();
+        let expected: Vec<&str> = full.trips.iter().map(|t| t.meta.id.as_str()).collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn test_trip_times() {
+        let resp = fixtures::response("sbb_api_response_0.json");
+
+        let trip = &resp.trips[0];
+        let dep = trip.departure_time().unwrap();
+        let arr = trip.arrival_time().unwrap();
+        assert_eq!(dep.to_rfc3339(), "2026-02-22T13:00:00+01:00");
+        assert_eq!((arr - dep).num_minutes(), 84);
+        assert_eq!(trip.duration_minutes(), Some(84));
+        assert_eq!(trip.summary.departure_anchor.quay_name(), Some("Gl. 19"));
+        assert_eq!(trip.summary.departure_anchor.delay_minutes(), Some(0));
+    }
+
+    #[test]
+    fn test_change_times() {
+        let resp = fixtures::response("sbb_api_response_0.json");
+
+        // Basel SBB 13:00 -> Olten 13:40, Olten 13:56 -> Bern
+        let trip = &resp.trips[0];
+        assert_eq!(trip.ride_legs().count(), 2);
+        let changes = trip.change_times();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].station, "Olten");
+        assert_eq!(changes[0].minutes, Some(16));
+        // The change at Olten is made on foot
+        assert_eq!(trip.walking_minutes(), 16);
+
+        // Direct trips have no changes
+        let resp = fixtures::response("sbb_api_response_3.json");
+        assert!(resp.trips[0].change_times().is_empty());
+    }
+
+    #[test]
+    fn test_coordinates_parsing() {
+        let resp = fixtures::response("sbb_api_response_0.json");
+
+        let trip = &resp.trips[0];
+        let dep = &trip.summary.departure_anchor;
+        assert_eq!(dep.place_reference.as_deref(), Some("8500010"));
+        let coords = dep.place_coordinates.as_ref().expect("coordinates missing");
+        assert!((coords.latitude - 47.547408).abs() < 1e-6);
+
+        // First leg Basel SBB -> Olten has intermediate stops on its companion card
+        let stops = trip.leg_stops(0);
+        assert!(stops.len() > 2);
+        assert_eq!(stops[0].place_reference.as_deref(), Some("8500010"));
+        let geometry = trip.leg_geometry(0);
+        assert!(geometry.len() > 2);
+        // Change legs carry no location
+        assert!(trip.leg_geometry(1).is_empty());
+        assert!(trip.leg_geometry(99).is_empty());
+    }
+
+    #[test]
+    fn test_rt_info_parsing() {
+        let resp = fixtures::response("sbb_api_response_2.json");
+
+        let trip = resp
+            .trips
+            .iter()
+            .find(|t| !t.disruptions().is_empty())
+            .expect("no trip with real-time info");
+        let disruptions = trip.disruptions();
+        assert!(disruptions.iter().all(|d| d.rt_type == RtType::Information));
+        assert!(disruptions.iter().any(|d| d.leg_index.is_some() && d.summary.is_some()));
+        assert!(!trip.is_cancelled());
+        assert!(!trip.has_platform_change());
+    }
+
+    #[test]
+    fn test_rt_types_aggregate() {
+        let leg = r#"{
+            "type": "PtRideLeg",
+            "departureStopPoint": {"displayName": "Olten", "quay": {"name": "Gl. 7", "changed": true}},
+            "arrivalStopPoint": {"displayName": "Bern"},
+            "rtTypes": ["CANCELLATION", "SOMETHING_NEW"],
+            "rtActionInfos": [{"rtType": "CANCELLATION", "summary": "Fällt aus"}]
+        }"#;
+        let trip: Trip = serde_json::from_str(&format!(
+            r#"{{
+                "meta": {{"id": "x"}},
+                "summary": {{
+                    "mostRelevantRtType": "CANCELLATION",
+                    "departureDisplayName": "Olten",
+                    "arrivalDisplayName": "Bern",
+                    "departureAnchor": {{"placeName": "Olten", "displayTime": "", "displayDate": ""}},
+                    "arrivalAnchor": {{"placeName": "Bern", "displayTime": "", "displayDate": ""}}
+                }},
+                "detail": {{"legs": [{}]}}
+            }}"#,
+            leg
+        ))
+        .unwrap();
+
+        assert!(trip.is_cancelled());
+        assert!(trip.has_platform_change());
+        let disruptions = trip.disruptions();
+        assert!(disruptions[0].rt_type.is_cancellation());
+        assert_eq!(disruptions[0].summary.as_deref(), Some("Fällt aus"));
+        assert!(disruptions.iter().any(|d| d.rt_type == RtType::Unknown("SOMETHING_NEW".to_string())));
+        // The summary type is already covered by the leg
+        assert!(disruptions.iter().all(|d| d.leg_index == Some(0)));
+    }
+
+    #[test]
+    fn test_rt_type_round_trip() {
+        let json = r#"["CANCELLED","CANCELLATION","PARTIAL_CANCELLATION","INFORMATION","SOMETHING_NEW"]"#;
+        let types: Vec<RtType> = serde_json::from_str(json).unwrap();
+        assert_eq!(types[1], RtType::Cancellation);
+        assert!(types[..3].iter().all(RtType::is_cancellation));
+        assert_eq!(types[4], RtType::Unknown("SOMETHING_NEW".to_string()));
+        assert_eq!(serde_json::to_string(&types).unwrap(), json);
+    }
 }
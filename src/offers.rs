@@ -0,0 +1,51 @@
+use simple_error::SimpleError;
+
+use crate::{get_json, Language};
+use crate::models::offer::{OfferResponse, TravellerOptions};
+use crate::models::trip::Trip;
+
+/// Fetch ticket offers for a trip returned by [`get_connections`](crate::connections::get_connections).
+///
+/// Prices depend on the traveller's class and travelcard, see [`TravellerOptions`].
+pub async fn get_offers(
+    trip: &Trip,
+    options: &TravellerOptions,
+    language: Option<Language>,
+) -> Result<OfferResponse, SimpleError> {
+    let class = options.travel_class.to_string();
+    let reduction = options.reduction.to_string();
+    let params = [("travelClass", class.as_str()), ("reduction", reduction.as_str())];
+
+    let path = format!("/api/timetable/v2/trips/{}/prices", trip.meta.id);
+    get_json(&path, &params, language).await
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, TimeZone, Utc};
+    use crate::connections::get_connections;
+    use crate::models::location::SearchDateTimeType;
+    use crate::models::offer::{Reduction, TravellerOptions};
+    use crate::offers::get_offers;
+
+    #[actix_rt::test]
+    #[ignore = "requires live API access"]
+    pub async fn test_get_offers_half_fare() {
+        let today = chrono::offset::Local::now();
+        let date = Utc.with_ymd_and_hms(today.year(), today.month(), today.day(), 12, 0, 0).unwrap();
+        let resp = get_connections(
+            "Zürich HB", Some("8503000"),
+            "Basel SBB", Some("8500010"),
+            &date,
+            SearchDateTimeType::Departure,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let options = TravellerOptions { reduction: Reduction::HalfFare, ..Default::default() };
+        let result = get_offers(&resp.trips[0], &options, None).await;
+        assert!(result.is_ok(), "error: {:?}", result.err());
+        println!("Cheapest: {:?}", result.unwrap().cheapest());
+    }
+}
@@ -0,0 +1,183 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+
+use crate::connections::get_connections;
+use crate::filter::TripFilter;
+use crate::models::location::{SearchDateTimeType, StationRef};
+use crate::models::trip::Trip;
+use crate::Language;
+
+/// The connection chosen for one day of a [`multi_day_overview`].
+#[derive(Serialize, Debug, Clone)]
+pub struct DaySummary {
+    pub date: NaiveDate,
+    /// Real-time departure and arrival, falling back to the scheduled times.
+    pub departure: Option<DateTime<FixedOffset>>,
+    pub arrival: Option<DateTime<FixedOffset>>,
+    pub duration_minutes: Option<i64>,
+    pub transfers: Option<usize>,
+    /// Line of the first ride, e.g. "IC 1".
+    pub line: Option<String>,
+    pub disruptions: Vec<String>,
+    /// Why the search failed, if it did.
+    pub error: Option<String>,
+    /// The chosen connection; `None` when no connection is suitable or the search failed.
+    #[serde(skip)]
+    pub trip: Option<Trip>,
+}
+
+impl DaySummary {
+    /// Summarize the suitable connection among the `trips` found for `date` at `time`:
+    /// the first one departing at or after `time` for departure searches, the last one
+    /// arriving at or before `time` for arrival searches. Cancelled and filtered out
+    /// connections are never suitable.
+    pub fn from_trips(
+        date: NaiveDate,
+        time: NaiveTime,
+        dt_type: SearchDateTimeType,
+        trips: &[Trip],
+        filter: &TripFilter,
+    ) -> DaySummary {
+        // Compare whole minutes, like the API does
+        let at = date.and_time(time.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(time));
+        let local = |t: Option<DateTime<FixedOffset>>| t.map(|t| t.naive_local());
+        let mut suitable = trips.iter().filter(|t| filter.matches(t) && !t.is_cancelled());
+        let trip = match dt_type {
+            SearchDateTimeType::Departure => suitable.find(|t| local(t.departure_time()).is_some_and(|d| d >= at)),
+            SearchDateTimeType::Arrival => suitable.rfind(|t| local(t.arrival_time()).is_some_and(|a| a <= at)),
+        };
+
+        DaySummary {
+            date,
+            departure: trip.and_then(|t| t.departure_time()),
+            arrival: trip.and_then(|t| t.arrival_time()),
+            duration_minutes: trip.and_then(|t| t.duration_minutes()),
+            transfers: trip.map(|t| t.transfers()),
+            line: trip.and_then(|t| t.ride_legs().next()?.first_transport_designation.as_ref().map(|d| d.to_string())),
+            disruptions: trip.map(|t| t.disruptions().iter().map(|d| d.to_string()).collect()).unwrap_or_default(),
+            error: None,
+            trip: trip.cloned(),
+        }
+    }
+
+    fn failed(date: NaiveDate, error: String) -> DaySummary {
+        DaySummary {
+            date,
+            departure: None,
+            arrival: None,
+            duration_minutes: None,
+            transfers: None,
+            line: None,
+            disruptions: Vec::new(),
+            error: Some(error),
+            trip: None,
+        }
+    }
+}
+
+/// Search the same route at the same local `time` on each of `dates`, with at most
+/// `max_concurrency` requests in flight, and summarize each day's suitable connection
+/// (see [`DaySummary::from_trips`]). The result is in the order of `dates`.
+#[allow(clippy::too_many_arguments)]
+pub async fn multi_day_overview(
+    from: &StationRef,
+    to: &StationRef,
+    dates: &[NaiveDate],
+    time: NaiveTime,
+    dt_type: SearchDateTimeType,
+    filter: &TripFilter,
+    max_concurrency: usize,
+    language: Option<Language>,
+) -> Vec<DaySummary> {
+    stream::iter(dates.iter().copied())
+        .map(|date| async move {
+            // Searches take the local wall-clock time
+            let on = Utc.from_utc_datetime(&NaiveDateTime::new(date, time));
+            let result = get_connections(
+                &from.name,
+                from.reference.as_deref(),
+                &to.name,
+                to.reference.as_deref(),
+                &on,
+                dt_type,
+                language,
+            )
+            .await;
+            match result {
+                Ok(resp) => DaySummary::from_trips(date, time, dt_type, &resp.trips, filter),
+                Err(e) => DaySummary::failed(date, e.to_string()),
+            }
+        })
+        .buffered(max_concurrency.max(1))
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::flat::{to_csv, to_ndjson, to_table};
+    use crate::models::trip::fixtures::trips;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 2, 22).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_departure_summary() {
+        let trips = trips("sbb_api_response_0.json");
+        let filter = TripFilter::default();
+        let day = DaySummary::from_trips(date(), time(13, 0), SearchDateTimeType::Departure, &trips, &filter);
+        assert_eq!(day.departure.unwrap().to_rfc3339(), "2026-02-22T13:00:00+01:00");
+        assert_eq!(day.duration_minutes, Some(84));
+        assert!(day.trip.is_some());
+
+        // Seconds of the search time don't skip the connection of that minute
+        let late = NaiveTime::from_hms_opt(13, 4, 30).unwrap();
+        let day = DaySummary::from_trips(date(), late, SearchDateTimeType::Departure, &trips, &filter);
+        assert_eq!(day.departure.unwrap().to_rfc3339(), "2026-02-22T13:04:00+01:00");
+
+        let direct = TripFilter { max_transfers: Some(0), ..Default::default() };
+        let day = DaySummary::from_trips(date(), time(13, 0), SearchDateTimeType::Departure, &trips, &direct);
+        assert_eq!(day.departure.unwrap().to_rfc3339(), "2026-02-22T13:30:00+01:00");
+        assert_eq!(day.transfers, Some(0));
+    }
+
+    #[test]
+    fn test_arrival_summary() {
+        let trips = trips("sbb_api_response_0.json");
+        let filter = TripFilter::default();
+        let day = DaySummary::from_trips(date(), time(14, 25), SearchDateTimeType::Arrival, &trips, &filter);
+        assert!(day.arrival.unwrap().to_rfc3339().starts_with("2026-02-22T14:24"));
+
+        let day = DaySummary::from_trips(date(), time(12, 0), SearchDateTimeType::Arrival, &trips, &filter);
+        assert!(day.trip.is_none());
+        assert!(day.departure.is_none());
+        assert!(day.error.is_none());
+    }
+
+    #[test]
+    fn test_day_rows() {
+        let trips = trips("sbb_api_response_0.json");
+        let filter = TripFilter::default();
+        let days = vec![
+            DaySummary::from_trips(date(), time(13, 0), SearchDateTimeType::Departure, &trips, &filter),
+            DaySummary::failed(date().succ_opt().unwrap(), "HTTP 500: oops".to_string()),
+        ];
+
+        let csv = to_csv(&days);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "date,departure,arrival,duration_minutes,transfers,line,disruptions,error");
+        assert!(lines[1].starts_with("2026-02-22,2026-02-22T13:00:00+01:00,"));
+        assert!(lines[2].ends_with(",,,,,,,HTTP 500: oops"));
+
+        let table = to_table(&days);
+        assert!(table.contains("Sun 22.02.  13:00"));
+        assert!(!to_ndjson(&days).contains("\"trip\""));
+    }
+}
@@ -0,0 +1,85 @@
+use simple_error::SimpleError;
+
+use crate::{get_json, Language};
+use crate::models::place::{Coordinates, NearbyPlace, PlaceSearchResponse};
+
+/// Search places (stations, addresses, POIs) by name.
+pub async fn get_places(query: &str, language: Option<Language>) -> Result<PlaceSearchResponse, SimpleError> {
+    get_json("/api/timetable/v2/places", &[("nameMatch", query)], language).await
+}
+
+/// Find stops within `radius_m` meters of the given position, nearest first.
+///
+/// Addresses and POIs are skipped; only places of type `STOP_PLACE` are returned.
+pub async fn nearby_places(
+    latitude: f64,
+    longitude: f64,
+    radius_m: f64,
+    language: Option<Language>,
+) -> Result<Vec<NearbyPlace>, SimpleError> {
+    let lat = latitude.to_string();
+    let lon = longitude.to_string();
+    let places: PlaceSearchResponse = get_json(
+        "/api/timetable/v2/places",
+        &[("latitude", lat.as_str()), ("longitude", lon.as_str())],
+        language,
+    )
+    .await?;
+
+    let origin = Coordinates { latitude, longitude };
+    Ok(sort_by_distance(places, &origin, radius_m))
+}
+
+fn sort_by_distance(places: PlaceSearchResponse, origin: &Coordinates, radius_m: f64) -> Vec<NearbyPlace> {
+    let mut nearby: Vec<NearbyPlace> = places
+        .into_iter()
+        .filter(|p| p.is_stop())
+        .map(|p| NearbyPlace {
+            distance_m: origin.distance_to(&p.coordinates),
+            place: p,
+        })
+        .filter(|n| n.distance_m <= radius_m)
+        .collect();
+    nearby.sort_by(|a, b| a.distance_m.total_cmp(&b.distance_m));
+    nearby
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::place::Place;
+
+    fn place(name: &str, place_type: &str, latitude: f64, longitude: f64) -> Place {
+        Place {
+            display_name: name.to_string(),
+            identifier: None,
+            place_type: place_type.to_string(),
+            coordinates: Coordinates { latitude, longitude },
+        }
+    }
+
+    #[test]
+    fn test_sort_by_distance() {
+        let origin = Coordinates { latitude: 47.378, longitude: 8.540 };
+        let places = vec![
+            place("Zürich, Central", "STOP_PLACE", 47.3769, 8.5440),
+            place("Zürich HB", "STOP_PLACE", 47.378177, 8.540193),
+            place("Bahnhofstrasse 1", "ADDRESS", 47.378, 8.540),
+            place("Winterthur", "STOP_PLACE", 47.5003, 8.7237),
+        ];
+        let nearby = sort_by_distance(places, &origin, 1000.0);
+        let names: Vec<&str> = nearby.iter().map(|n| n.place.display_name.as_str()).collect();
+        assert_eq!(names, vec!["Zürich HB", "Zürich, Central"]);
+        assert!(nearby[0].distance_m < nearby[1].distance_m);
+    }
+
+    #[actix_rt::test]
+    #[ignore = "requires live API access"]
+    pub async fn test_nearby_places_zurich() {
+        let result = nearby_places(47.378, 8.540, 500.0, None).await;
+        assert!(result.is_ok(), "error: {:?}", result.err());
+        for n in result.unwrap() {
+            println!("{:.0} m  {}", n.distance_m, n.place.display_name);
+        }
+    }
+}
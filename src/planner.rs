@@ -0,0 +1,132 @@
+use chrono::{Duration, NaiveDateTime};
+use simple_error::SimpleError;
+
+use crate::connections::find_connection;
+use crate::filter::TripFilter;
+use crate::models::location::{SearchDateTimeType, StationRef};
+use crate::models::trip::{PageDirection, Trip};
+use crate::Language;
+
+/// How much margin an arrive-by plan needs.
+#[derive(Debug, Clone)]
+pub struct ArriveByOptions {
+    /// Minutes to arrive before the deadline, e.g. to walk to a meeting.
+    pub buffer_minutes: i64,
+    /// Changes shorter than this are too risky to rely on. Ignored when `filter`
+    /// sets its own `min_change_minutes`.
+    pub min_change_minutes: i64,
+    /// Further criteria the connection must meet.
+    pub filter: TripFilter,
+    /// Language of the planned connection's texts; German when `None`.
+    pub language: Option<Language>,
+}
+
+impl Default for ArriveByOptions {
+    fn default() -> Self {
+        ArriveByOptions {
+            buffer_minutes: 0,
+            min_change_minutes: 4,
+            filter: TripFilter::default(),
+            language: None,
+        }
+    }
+}
+
+impl ArriveByOptions {
+    /// Latest acceptable arrival for a `deadline`.
+    pub fn latest_arrival(&self, deadline: NaiveDateTime) -> NaiveDateTime {
+        deadline - Duration::minutes(self.buffer_minutes)
+    }
+
+    /// Whether `trip` gets there in time for `deadline` without risky changes.
+    /// Real-time data counts: a delayed arrival or a change shortened by a delay can make a trip unsafe.
+    pub fn is_safe(&self, trip: &Trip, deadline: NaiveDateTime) -> bool {
+        let mut filter = self.filter.clone();
+        filter.min_change_minutes = filter.min_change_minutes.or(Some(self.min_change_minutes));
+        !trip.is_cancelled()
+            && filter.matches(trip)
+            && trip
+                .arrival_time()
+                .is_some_and(|a| a.naive_local() <= self.latest_arrival(deadline))
+    }
+}
+
+/// The safe trip departing latest (ties: arriving earliest), see [`ArriveByOptions::is_safe`].
+pub fn latest_safe_trip<'a>(trips: &'a [Trip], deadline: NaiveDateTime, options: &ArriveByOptions) -> Option<&'a Trip> {
+    trips
+        .iter()
+        .filter(|t| options.is_safe(t, deadline))
+        .max_by_key(|t| (t.departure_time(), std::cmp::Reverse(t.arrival_time())))
+}
+
+/// Plan a trip that must arrive by `deadline` (local time): the latest safe departure.
+///
+/// Runs an arrival search for `deadline` minus the buffer, and looks at earlier
+/// connections when none of the first ones is safe.
+pub async fn plan_arrive_by(
+    from: &StationRef,
+    to: &StationRef,
+    deadline: NaiveDateTime,
+    options: &ArriveByOptions,
+) -> Result<Option<Trip>, SimpleError> {
+    find_connection(
+        from,
+        to,
+        options.latest_arrival(deadline),
+        SearchDateTimeType::Arrival,
+        PageDirection::Earlier,
+        options.language,
+        |trips| latest_safe_trip(trips, deadline, options),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::{at, trips};
+
+    fn departure(trip: Option<&Trip>) -> Option<&str> {
+        trip?.summary.departure_anchor.time_aimed.as_deref()
+    }
+
+    #[test]
+    fn test_latest_safe_trip() {
+        let trips = trips("sbb_api_response_0.json");
+        let options = ArriveByOptions::default();
+        assert_eq!(departure(latest_safe_trip(&trips, at(15, 0), &options)), Some("2026-02-22T13:58:00+01:00"));
+        // 13:58 arrives at 14:56
+        assert_eq!(departure(latest_safe_trip(&trips, at(14, 55), &options)), Some("2026-02-22T13:30:00+01:00"));
+
+        // 15 minutes of buffer before 14:40 means arriving by 14:25
+        let buffered = ArriveByOptions { buffer_minutes: 15, ..Default::default() };
+        assert_eq!(buffered.latest_arrival(at(14, 40)), at(14, 25));
+        assert_eq!(departure(latest_safe_trip(&trips, at(14, 40), &buffered)), Some("2026-02-22T13:16:00+01:00"));
+
+        assert!(latest_safe_trip(&trips, at(14, 0), &options).is_none());
+    }
+
+    #[test]
+    fn test_risky_changes() {
+        let trips = trips("sbb_api_response_0.json");
+        let deadline = at(14, 25);
+        let shortest = trips
+            .iter()
+            .filter(|t| t.transfers() > 0)
+            .flat_map(|t| t.change_times())
+            .filter_map(|c| c.minutes)
+            .min()
+            .unwrap();
+
+        // Requiring more than the shortest change drops at least one trip
+        let strict = ArriveByOptions { min_change_minutes: shortest + 1, ..Default::default() };
+        let lenient = ArriveByOptions { min_change_minutes: 0, ..Default::default() };
+        let safe = |o: &ArriveByOptions| trips.iter().filter(|t| o.is_safe(t, deadline)).count();
+        assert!(safe(&strict) < safe(&lenient));
+
+        // A minimum set on the filter takes precedence
+        let filter = TripFilter { min_change_minutes: Some(0), ..Default::default() };
+        let overridden = ArriveByOptions { min_change_minutes: 60, filter, ..Default::default() };
+        assert_eq!(safe(&overridden), safe(&lenient));
+    }
+}
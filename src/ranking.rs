@@ -0,0 +1,167 @@
+use std::cmp::Ordering;
+
+use crate::models::trip::Trip;
+
+/// A property by which trips can be compared. Lower values are always better.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Criterion {
+    /// Travel time.
+    Duration,
+    /// Number of transfers.
+    Transfers,
+    /// Real-time arrival, earlier is better.
+    Arrival,
+    /// Highest expected second-class occupancy along the trip.
+    Occupancy,
+    /// Total walking time.
+    Walking,
+}
+
+impl Criterion {
+    /// Cost of `trip` under this criterion, `None` when the trip lacks the data.
+    pub fn cost(&self, trip: &Trip) -> Option<i64> {
+        match self {
+            Criterion::Duration => trip.duration_minutes(),
+            Criterion::Transfers => Some(trip.transfers() as i64),
+            Criterion::Arrival => trip.arrival_time().map(|t| t.timestamp()),
+            Criterion::Occupancy => occupancy_level(trip.summary.occupancy_second_class_max.as_deref()),
+            Criterion::Walking => Some(trip.walking_minutes()),
+        }
+    }
+}
+
+/// Numeric level of an occupancy value: "LOW" = 1, "MEDIUM" = 2, "HIGH" = 3.
+pub fn occupancy_level(occupancy: Option<&str>) -> Option<i64> {
+    match occupancy? {
+        "LOW" => Some(1),
+        "MEDIUM" => Some(2),
+        "HIGH" => Some(3),
+        _ => None,
+    }
+}
+
+// Missing data sorts last
+fn cost_or_worst(criterion: &Criterion, trip: &Trip) -> i64 {
+    criterion.cost(trip).unwrap_or(i64::MAX)
+}
+
+/// Compare two trips lexicographically by `criteria`.
+pub fn compare(a: &Trip, b: &Trip, criteria: &[Criterion]) -> Ordering {
+    criteria
+        .iter()
+        .map(|c| cost_or_worst(c, a).cmp(&cost_or_worst(c, b)))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Sort trips by `criteria`, the first criterion being the most significant.
+/// The sort is stable, so ties keep the API (departure) order.
+pub fn sort_trips(trips: &mut [Trip], criteria: &[Criterion]) {
+    trips.sort_by(|a, b| compare(a, b, criteria));
+}
+
+/// Whether `a` is at least as good as `b` under every criterion and strictly better under one.
+pub fn dominates(a: &Trip, b: &Trip, criteria: &[Criterion]) -> bool {
+    let mut strictly_better = false;
+    for c in criteria {
+        match cost_or_worst(c, a).cmp(&cost_or_worst(c, b)) {
+            Ordering::Greater => return false,
+            Ordering::Less => strictly_better = true,
+            Ordering::Equal => {}
+        }
+    }
+    strictly_better
+}
+
+/// Drop every trip dominated by another one, keeping the Pareto front under `criteria`.
+/// The remaining trips keep their relative order.
+pub fn retain_pareto_front(trips: &mut Vec<Trip>, criteria: &[Criterion]) {
+    let keep: Vec<bool> = trips
+        .iter()
+        .map(|t| !trips.iter().any(|other| dominates(other, t, criteria)))
+        .collect();
+    let mut keep = keep.into_iter();
+    trips.retain(|_| keep.next().unwrap_or(true));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+
+    fn trip(id: &str, dep: &str, arr: &str, minutes: i32, occupancy: &str, ride_legs: usize) -> Trip {
+        let leg = r#"{"type": "PtRideLeg", "departureStopPoint": {"displayName": "A"}, "arrivalStopPoint": {"displayName": "B"}}"#;
+        let legs = vec![leg; ride_legs].join(",");
+        serde_json::from_str(&format!(
+            r#"{{
+                "meta": {{"id": "{id}"}},
+                "summary": {{
+                    "duration": {{"localizedLabel": "", "durationInMinutes": {minutes}}},
+                    "occupancySecondClassMax": "{occupancy}",
+                    "departureDisplayName": "A",
+                    "arrivalDisplayName": "B",
+                    "departureAnchor": {{"placeName": "A", "timeAimed": "2026-02-22T{dep}:00+01:00", "displayTime": "", "displayDate": ""}},
+                    "arrivalAnchor": {{"placeName": "B", "timeAimed": "2026-02-22T{arr}:00+01:00", "displayTime": "", "displayDate": ""}}
+                }},
+                "detail": {{"legs": [{legs}]}}
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    fn ids(trips: &[Trip]) -> Vec<&str> {
+        trips.iter().map(|t| t.meta.id.as_str()).collect()
+    }
+
+    fn sample() -> Vec<Trip> {
+        vec![
+            trip("slow-direct", "10:00", "11:30", 90, "LOW", 1),
+            trip("fast-change", "10:05", "11:05", 60, "HIGH", 2),
+            trip("dominated", "10:10", "11:45", 95, "MEDIUM", 2),
+            trip("fast-direct", "10:30", "11:30", 60, "MEDIUM", 1),
+        ]
+    }
+
+    #[test]
+    fn test_sort_trips() {
+        let mut trips = sample();
+        sort_trips(&mut trips, &[Criterion::Duration]);
+        assert_eq!(ids(&trips), vec!["fast-change", "fast-direct", "slow-direct", "dominated"]);
+
+        // Both direct trips arrive at 11:30 and keep their previous order
+        sort_trips(&mut trips, &[Criterion::Transfers, Criterion::Arrival]);
+        assert_eq!(ids(&trips), vec!["fast-direct", "slow-direct", "fast-change", "dominated"]);
+
+        sort_trips(&mut trips, &[Criterion::Occupancy]);
+        assert_eq!(ids(&trips)[0], "slow-direct");
+    }
+
+    #[test]
+    fn test_pareto_front() {
+        let mut trips = sample();
+        retain_pareto_front(&mut trips, &[Criterion::Duration, Criterion::Transfers]);
+        assert_eq!(ids(&trips), vec!["fast-direct"]);
+
+        let mut trips = sample();
+        retain_pareto_front(&mut trips, &[Criterion::Duration, Criterion::Transfers, Criterion::Arrival]);
+        assert_eq!(ids(&trips), vec!["fast-change", "fast-direct"]);
+
+        // Identical trips don't dominate each other
+        let mut trips = vec![sample().remove(0), sample().remove(0)];
+        retain_pareto_front(&mut trips, &[Criterion::Duration]);
+        assert_eq!(trips.len(), 2);
+    }
+
+    #[test]
+    fn test_pareto_front_fixture() {
+        let mut trips = trips("sbb_api_response_2.json");
+        let before = trips.len();
+
+        let criteria = [Criterion::Duration, Criterion::Transfers, Criterion::Arrival];
+        retain_pareto_front(&mut trips, &criteria);
+        assert!(!trips.is_empty() && trips.len() <= before);
+        for a in &trips {
+            assert!(trips.iter().all(|b| !dominates(b, a, &criteria)));
+        }
+    }
+}
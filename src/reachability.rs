@@ -0,0 +1,264 @@
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use simple_error::SimpleError;
+
+use crate::connections::ConnectionCache;
+use crate::models::location::{SearchDateTimeType, StationRef};
+use crate::models::trip::Trip;
+use crate::Language;
+
+/// Which connection counts as the fastest to a destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankBy {
+    /// Earliest arrival; the shorter ride wins a tie.
+    Arrival,
+    /// Shortest ride; the earlier arrival wins a tie.
+    Duration,
+}
+
+/// Limits applied when querying many destinations.
+#[derive(Debug, Clone, Copy)]
+pub struct ReachabilityOptions {
+    /// How the connections departing within the window are compared.
+    pub rank_by: RankBy,
+    /// Maximum number of searches in flight at once.
+    pub max_concurrency: usize,
+    /// Maximum number of searches per destination to cover the departure window.
+    pub max_searches_per_destination: usize,
+    /// Language of the connections' texts; German when `None`.
+    pub language: Option<Language>,
+}
+
+impl Default for ReachabilityOptions {
+    fn default() -> Self {
+        ReachabilityOptions {
+            rank_by: RankBy::Arrival,
+            max_concurrency: 4,
+            max_searches_per_destination: 3,
+            language: None,
+        }
+    }
+}
+
+/// The fastest connection found to a destination.
+#[derive(Serialize, Debug, Clone)]
+pub struct ReachableTrip {
+    pub trip_id: String,
+    pub departure: DateTime<FixedOffset>,
+    pub arrival: DateTime<FixedOffset>,
+    pub duration_minutes: i64,
+    pub transfers: usize,
+}
+
+/// One row of a [`ReachabilityTable`].
+#[derive(Serialize, Debug, Clone)]
+pub struct ReachabilityEntry {
+    pub destination: StationRef,
+    /// `None` when no connection departs within the window.
+    pub fastest: Option<ReachableTrip>,
+    /// Set when searching this destination failed.
+    pub error: Option<String>,
+}
+
+/// Travel times from one origin to many destinations.
+#[derive(Serialize, Debug, Clone)]
+pub struct ReachabilityTable {
+    pub origin: StationRef,
+    pub entries: Vec<ReachabilityEntry>,
+}
+
+impl ReachabilityTable {
+    /// Sort by travel time, shortest first. Unreachable destinations go last.
+    pub fn sort_by_duration(&mut self) {
+        self.entries
+            .sort_by_key(|e| e.fastest.as_ref().map(|f| f.duration_minutes).unwrap_or(i64::MAX));
+    }
+
+    /// Sort by arrival time, earliest first. Unreachable destinations go last.
+    pub fn sort_by_arrival(&mut self) {
+        self.entries
+            .sort_by_key(|e| e.fastest.as_ref().map(|f| f.arrival.timestamp()).unwrap_or(i64::MAX));
+    }
+
+    /// Sort alphabetically by destination.
+    pub fn sort_by_destination(&mut self) {
+        self.entries.sort_by_key(|e| e.destination.to_string());
+    }
+}
+
+/// Compute the fastest connection from `origin` to each destination, departing between
+/// `window_start` and `window_end`: local wall-clock times, passed as UTC like the `on`
+/// of [`crate::connections::get_connections`]. By default the fastest is the one arriving
+/// first, see [`ReachabilityOptions::rank_by`].
+///
+/// Searches run with at most `options.max_concurrency` requests in flight and go through
+/// `cache`, so overlapping calls don't repeat identical requests. A failed destination is
+/// reported in its entry instead of failing the whole table.
+pub async fn reachability(
+    origin: &StationRef,
+    destinations: &[StationRef],
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+    options: &ReachabilityOptions,
+    cache: &ConnectionCache,
+) -> ReachabilityTable {
+    let entries = stream::iter(destinations)
+        .map(|destination| async move {
+            match fastest_to(origin, destination, window_start, window_end, options, cache).await {
+                Ok(fastest) => ReachabilityEntry { destination: destination.clone(), fastest, error: None },
+                Err(e) => ReachabilityEntry {
+                    destination: destination.clone(),
+                    fastest: None,
+                    error: Some(e.to_string()),
+                },
+            }
+        })
+        .buffered(options.max_concurrency.max(1))
+        .collect()
+        .await;
+
+    ReachabilityTable { origin: origin.clone(), entries }
+}
+
+pub(crate) async fn fastest_to(
+    origin: &StationRef,
+    destination: &StationRef,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+    options: &ReachabilityOptions,
+    cache: &ConnectionCache,
+) -> Result<Option<ReachableTrip>, SimpleError> {
+    let mut best: Option<ReachableTrip> = None;
+    let mut search_at = window_start;
+
+    // Each search returns a handful of departures; step past the last one until the window is covered
+    for _ in 0..options.max_searches_per_destination.max(1) {
+        let resp = cache
+            .get_connections(origin, destination, &search_at, SearchDateTimeType::Departure, options.language)
+            .await?;
+
+        if let Some(candidate) = fastest_in_window(&resp.trips, window_start, window_end, options.rank_by) {
+            if best.as_ref().map(|b| is_better(&candidate, b, options.rank_by)).unwrap_or(true) {
+                best = Some(candidate);
+            }
+        }
+
+        match resp.trips.iter().filter_map(Trip::departure_time).max() {
+            Some(last) if last.naive_local() < window_end.naive_utc() => {
+                search_at = Utc.from_utc_datetime(&(last.naive_local() + Duration::minutes(1)));
+            }
+            _ => break,
+        }
+    }
+
+    Ok(best)
+}
+
+/// The best of `trips` departing within the window, as ranked by `rank_by`.
+///
+/// Like the searches, the window holds local wall-clock time in a `DateTime<Utc>`.
+pub(crate) fn fastest_in_window(
+    trips: &[Trip],
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+    rank_by: RankBy,
+) -> Option<ReachableTrip> {
+    trips
+        .iter()
+        .filter(|t| !t.is_cancelled())
+        .filter_map(|t| {
+            let departure = t.departure_time()?;
+            let arrival = t.arrival_time()?;
+            let local = departure.naive_local();
+            let in_window = local >= window_start.naive_utc() && local <= window_end.naive_utc();
+            in_window.then(|| ReachableTrip {
+                trip_id: t.meta.id.clone(),
+                departure,
+                arrival,
+                duration_minutes: t.duration_minutes().unwrap_or((arrival - departure).num_minutes()),
+                transfers: t.transfers(),
+            })
+        })
+        .reduce(|a, b| if is_better(&b, &a, rank_by) { b } else { a })
+}
+
+fn is_better(a: &ReachableTrip, b: &ReachableTrip, rank_by: RankBy) -> bool {
+    match rank_by {
+        RankBy::Arrival => (a.arrival, a.duration_minutes) < (b.arrival, b.duration_minutes),
+        RankBy::Duration => (a.duration_minutes, a.arrival) < (b.duration_minutes, b.arrival),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+
+    #[test]
+    fn test_fastest_in_window() {
+        let trips = trips("sbb_api_response_2.json");
+        let start = Utc.with_ymd_and_hms(2026, 2, 22, 8, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 2, 22, 12, 0, 0).unwrap();
+
+        let fastest = fastest_in_window(&trips, start, end, RankBy::Arrival).expect("no trip in window");
+        let earliest = trips.iter().filter_map(Trip::arrival_time).min().unwrap();
+        assert_eq!(fastest.arrival, earliest);
+
+        // Between 10:30 and 11:10, the 10:35 (173 min) arrives first, the 11:05 (171 min) is shorter
+        let start = Utc.with_ymd_and_hms(2026, 2, 22, 10, 30, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 2, 22, 11, 10, 0).unwrap();
+        let first = fastest_in_window(&trips, start, end, RankBy::Arrival).expect("no trip in window");
+        assert_eq!(first.arrival.to_rfc3339(), "2026-02-22T13:28:00+01:00");
+        let shortest = fastest_in_window(&trips, start, end, RankBy::Duration).expect("no trip in window");
+        assert_eq!(shortest.departure.to_rfc3339(), "2026-02-22T11:05:00+01:00");
+        assert_eq!(shortest.duration_minutes, 171);
+
+        // Nothing departs in an empty window
+        assert!(fastest_in_window(&trips, end, start, RankBy::Arrival).is_none());
+    }
+
+    #[test]
+    fn test_window_is_local_time() {
+        let trips = trips("sbb_api_response_2.json");
+        // The 10:05+01:00 departure is at the very start of a 10:05 window, not an hour before it
+        let start = Utc.with_ymd_and_hms(2026, 2, 22, 10, 5, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 2, 22, 10, 30, 0).unwrap();
+        let fastest = fastest_in_window(&trips, start, end, RankBy::Arrival).expect("no trip in window");
+        assert_eq!(fastest.departure.to_rfc3339(), "2026-02-22T10:05:00+01:00");
+
+        // ... and at the very end of a window closing at 12:05
+        let start = Utc.with_ymd_and_hms(2026, 2, 22, 11, 50, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 2, 22, 12, 5, 0).unwrap();
+        let fastest = fastest_in_window(&trips, start, end, RankBy::Arrival).expect("no trip in window");
+        assert_eq!(fastest.departure.to_rfc3339(), "2026-02-22T12:05:00+01:00");
+    }
+
+    #[test]
+    fn test_table_sorting() {
+        let at = |h| FixedOffset::east_opt(3600).unwrap().with_ymd_and_hms(2026, 2, 22, h, 0, 0).unwrap();
+        let entry = |name: &str, duration: Option<i64>, arrival_hour| ReachabilityEntry {
+            destination: StationRef::new(name, None),
+            fastest: duration.map(|d| ReachableTrip {
+                trip_id: name.to_string(),
+                departure: at(8),
+                arrival: at(arrival_hour),
+                duration_minutes: d,
+                transfers: 0,
+            }),
+            error: None,
+        };
+        let mut table = ReachabilityTable {
+            origin: StationRef::new("Zürich HB", None),
+            entries: vec![entry("Bern", Some(56), 10), entry("Chur", None, 0), entry("Basel SBB", Some(53), 11)],
+        };
+
+        table.sort_by_duration();
+        let names: Vec<String> = table.entries.iter().map(|e| e.destination.to_string()).collect();
+        assert_eq!(names, vec!["Basel SBB", "Bern", "Chur"]);
+
+        table.sort_by_arrival();
+        assert_eq!(table.entries[0].destination.name, "Bern");
+        assert_eq!(table.entries[2].destination.name, "Chur");
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use simple_error::SimpleError;
+
+use crate::connections::find_connection;
+use crate::filter::TripFilter;
+use crate::models::location::{SearchDateTimeType, StationRef};
+use crate::models::trip::{PageDirection, Trip};
+use crate::Language;
+
+/// Hour at which a service day starts. Trains running after midnight until then
+/// still belong to the previous day, as in the printed timetable.
+pub const SERVICE_DAY_START_HOUR: u32 = 4;
+
+/// Start and end (exclusive) of the service day `date`, in local time:
+/// from 04:00 on `date` to 04:00 on the next day.
+pub fn service_day_bounds(date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    let start = date.and_time(NaiveTime::from_hms_opt(SERVICE_DAY_START_HOUR, 0, 0).expect("valid hour"));
+    (start, start + Duration::days(1))
+}
+
+/// The service day a local time belongs to: 01:30 on a Saturday is still Friday's.
+pub fn service_day_of(time: NaiveDateTime) -> NaiveDate {
+    (time - Duration::hours(SERVICE_DAY_START_HOUR as i64)).date()
+}
+
+/// The last connection of the service day `date`: the latest departure that still
+/// arrives before the service day ends, after midnight included.
+///
+/// Searches backwards by arrival from the end of the service day.
+pub async fn last_connection(
+    from: &StationRef,
+    to: &StationRef,
+    date: NaiveDate,
+    filter: &TripFilter,
+    language: Option<Language>,
+) -> Result<Option<Trip>, SimpleError> {
+    let (start, end) = service_day_bounds(date);
+    // The API takes minutes, and arrival searches include the given minute
+    let on = end - Duration::minutes(1);
+    find_connection(from, to, on, SearchDateTimeType::Arrival, PageDirection::Earlier, language, |trips| {
+        pick_last(trips, start, end, filter)
+    })
+    .await
+}
+
+/// The first connection of the service day `date`: the earliest arrival of those
+/// departing after the service day starts.
+///
+/// Searches forwards by departure from the start of the service day.
+pub async fn first_connection(
+    from: &StationRef,
+    to: &StationRef,
+    date: NaiveDate,
+    filter: &TripFilter,
+    language: Option<Language>,
+) -> Result<Option<Trip>, SimpleError> {
+    let (start, end) = service_day_bounds(date);
+    find_connection(from, to, start, SearchDateTimeType::Departure, PageDirection::Later, language, |trips| {
+        pick_first(trips, start, end, filter)
+    })
+    .await
+}
+
+fn local(time: Option<DateTime<FixedOffset>>) -> Option<NaiveDateTime> {
+    time.map(|t| t.naive_local())
+}
+
+/// Whether `trip` runs entirely within `start..end`, is not cancelled and matches `filter`
+fn suitable(trip: &Trip, start: NaiveDateTime, end: NaiveDateTime, filter: &TripFilter) -> bool {
+    !trip.is_cancelled()
+        && filter.matches(trip)
+        && local(trip.departure_time()).is_some_and(|d| d >= start)
+        && local(trip.arrival_time()).is_some_and(|a| a < end)
+}
+
+/// The trip departing latest (ties: arriving earliest) within the service day.
+pub fn pick_last<'a>(trips: &'a [Trip], start: NaiveDateTime, end: NaiveDateTime, filter: &TripFilter) -> Option<&'a Trip> {
+    trips
+        .iter()
+        .filter(|t| suitable(t, start, end, filter))
+        .max_by_key(|t| (t.departure_time(), std::cmp::Reverse(t.arrival_time())))
+}
+
+/// The trip arriving earliest (ties: departing latest) within the service day.
+pub fn pick_first<'a>(trips: &'a [Trip], start: NaiveDateTime, end: NaiveDateTime, filter: &TripFilter) -> Option<&'a Trip> {
+    trips
+        .iter()
+        .filter(|t| suitable(t, start, end, filter))
+        .min_by_key(|t| (t.arrival_time(), std::cmp::Reverse(t.departure_time())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::{at, trips};
+
+    #[test]
+    fn test_service_day_bounds() {
+        let (start, end) = service_day_bounds(NaiveDate::from_ymd_opt(2026, 2, 22).unwrap());
+        assert_eq!(start, at(4, 0));
+        assert_eq!(end.to_string(), "2026-02-23 04:00:00");
+
+        assert_eq!(service_day_of(at(4, 0)), start.date());
+        assert_eq!(service_day_of(at(23, 59)), start.date());
+        assert_eq!(service_day_of(at(3, 59)).to_string(), "2026-02-21");
+    }
+
+    #[test]
+    fn test_pick_last_and_first() {
+        let trips = trips("sbb_api_response_0.json");
+        let filter = TripFilter::default();
+        let (start, end) = service_day_bounds(at(0, 0).date());
+
+        let last = pick_last(&trips, start, end, &filter).unwrap();
+        assert_eq!(last.summary.departure_anchor.time_aimed.as_deref(), Some("2026-02-22T13:58:00+01:00"));
+
+        // 13:30 → 14:26 arrives later than the 13:04 → 14:21
+        let first = pick_first(&trips, start, end, &filter).unwrap();
+        assert_eq!(first.summary.departure_anchor.time_aimed.as_deref(), Some("2026-02-22T13:04:00+01:00"));
+
+        // A day ending before the last arrival
+        let last = pick_last(&trips, start, at(14, 30), &filter).unwrap();
+        assert_eq!(last.summary.departure_anchor.time_aimed.as_deref(), Some("2026-02-22T13:30:00+01:00"));
+
+        let direct = TripFilter { max_transfers: Some(0), ..Default::default() };
+        let first = pick_first(&trips, start, end, &direct).unwrap();
+        assert_eq!(first.summary.departure_anchor.time_aimed.as_deref(), Some("2026-02-22T13:30:00+01:00"));
+
+        assert!(pick_first(&trips, at(14, 0), end, &filter).is_none());
+    }
+}
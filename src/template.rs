@@ -0,0 +1,323 @@
+use simple_error::SimpleError;
+use std::str::FromStr;
+
+use crate::datetime::format_clock_time;
+use crate::models::trip::{parse_time, PtRideLeg, StopTime, Trip, TripLeg};
+
+/// Fields available at the top level of a template.
+pub const TRIP_FIELDS: &[&str] = &[
+    "from",
+    "to",
+    "date",
+    "departure",
+    "departure_aimed",
+    "arrival",
+    "arrival_aimed",
+    "delay",
+    "arrival_delay",
+    "quay",
+    "arrival_quay",
+    "platform_changed",
+    "line",
+    "direction",
+    "duration",
+    "transfers",
+    "occupancy",
+    "occupancy_first_class",
+    "cancelled",
+];
+
+/// Fields available inside `{#legs}…{/legs}`, in addition to the trip fields.
+pub const LEG_FIELDS: &[&str] = &[
+    "from",
+    "to",
+    "departure",
+    "departure_aimed",
+    "arrival",
+    "arrival_aimed",
+    "delay",
+    "arrival_delay",
+    "quay",
+    "arrival_quay",
+    "platform_changed",
+    "line",
+    "direction",
+    "occupancy",
+    "occupancy_first_class",
+    "cancelled",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Field(String),
+    /// Field name, inverted, content
+    Section(String, bool, Vec<Node>),
+    Legs(Vec<Node>),
+}
+
+/// A small template language for one-line summaries of trips, e.g. for status bars.
+///
+/// - `{field}` inserts a field (see [`TRIP_FIELDS`]), or nothing when it is unknown for this trip.
+/// - `{?field}…{/field}` renders its content only when the field is set (non-empty),
+///   `{!field}…{/field}` only when it is not.
+/// - `{#legs}…{/legs}` repeats its content for every ride leg, where [`LEG_FIELDS`] apply.
+/// - `{{` and `}}` are literal braces.
+///
+/// `{line} {departure}{?delay} ({delay}){/delay} {quay}` renders e.g. `IC 8 10:32 (+3) Gl. 7`:
+/// quays come with their prefix, as named by the API.
+/// Times are `HH:MM`, real-time when known; delays are signed minutes and empty when on time;
+/// flags such as `cancelled` are `yes` or empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl FromStr for Template {
+    type Err = SimpleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars().peekable();
+        let nodes = parse_nodes(&mut chars, None, false)?;
+        Ok(Template { nodes })
+    }
+}
+
+impl Template {
+    /// Render the template for one trip.
+    pub fn render(&self, trip: &Trip) -> String {
+        let fields = trip_fields(trip);
+        let mut out = String::new();
+        render_nodes(&self.nodes, trip, &fields, &[], &mut out);
+        out
+    }
+}
+
+type Field = (&'static str, String);
+
+fn parse_nodes(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    closing: Option<&str>,
+    in_legs: bool,
+) -> Result<Vec<Node>, SimpleError> {
+    let mut nodes = Vec::new();
+    let mut text = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '}' => return Err(SimpleError::new("unmatched `}`, write `}}` for a literal brace")),
+            '{' => {
+                let mut tag = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => tag.push(c),
+                        None => return Err(SimpleError::new(format!("unclosed `{{{}`", tag))),
+                    }
+                }
+                if !text.is_empty() {
+                    nodes.push(Node::Text(std::mem::take(&mut text)));
+                }
+
+                let tag = tag.trim();
+                if let Some(name) = tag.strip_prefix('/') {
+                    return match closing {
+                        Some(expected) if expected == name => Ok(nodes),
+                        Some(expected) => Err(SimpleError::new(format!(
+                            "expected `{{/{}}}`, found `{{/{}}}`",
+                            expected, name
+                        ))),
+                        None => Err(SimpleError::new(format!("`{{/{}}}` without opening tag", name))),
+                    };
+                }
+
+                if tag == "#legs" {
+                    if in_legs {
+                        return Err(SimpleError::new("`{#legs}` cannot be nested"));
+                    }
+                    nodes.push(Node::Legs(parse_nodes(chars, Some("legs"), true)?));
+                } else if let Some(name) = tag.strip_prefix('?').or_else(|| tag.strip_prefix('!')) {
+                    check_field(name, in_legs)?;
+                    let inverted = tag.starts_with('!');
+                    let content = parse_nodes(chars, Some(name), in_legs)?;
+                    nodes.push(Node::Section(name.to_string(), inverted, content));
+                } else {
+                    check_field(tag, in_legs)?;
+                    nodes.push(Node::Field(tag.to_string()));
+                }
+            }
+            c => text.push(c),
+        }
+    }
+
+    if let Some(name) = closing {
+        return Err(SimpleError::new(format!("missing `{{/{}}}`", name)));
+    }
+    if !text.is_empty() {
+        nodes.push(Node::Text(text));
+    }
+    Ok(nodes)
+}
+
+fn check_field(name: &str, in_legs: bool) -> Result<(), SimpleError> {
+    if TRIP_FIELDS.contains(&name) || (in_legs && LEG_FIELDS.contains(&name)) {
+        Ok(())
+    } else {
+        Err(SimpleError::new(format!("unknown template field `{}`", name)))
+    }
+}
+
+fn render_nodes(nodes: &[Node], trip: &Trip, trip_fields: &[Field], leg_fields: &[Field], out: &mut String) {
+    // Leg fields shadow trip fields inside `{#legs}`
+    let lookup = |name: &str| {
+        leg_fields
+            .iter()
+            .chain(trip_fields.iter())
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.as_str())
+            .unwrap_or("")
+    };
+
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(t),
+            Node::Field(name) => out.push_str(lookup(name)),
+            Node::Section(name, inverted, content) => {
+                if lookup(name).is_empty() == *inverted {
+                    render_nodes(content, trip, trip_fields, leg_fields, out);
+                }
+            }
+            Node::Legs(content) => {
+                let cancelled_legs: Vec<Option<usize>> = trip
+                    .disruptions()
+                    .into_iter()
+                    .filter(|d| d.rt_type.is_cancellation())
+                    .map(|d| d.leg_index)
+                    .collect();
+                let legs = trip.detail.as_ref().map(|d| d.legs.as_slice()).unwrap_or_default();
+                for (idx, leg) in legs.iter().enumerate() {
+                    if let TripLeg::PtRideLeg(pt) = leg {
+                        let cancelled = cancelled_legs.iter().any(|l| l.is_none_or(|i| i == idx));
+                        render_nodes(content, trip, trip_fields, &leg_fields_of(pt, cancelled), out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn signed(delay: Option<i64>) -> String {
+    delay.filter(|d| *d != 0).map(|d| format!("{:+}", d)).unwrap_or_default()
+}
+
+fn flag(set: bool) -> String {
+    if set { "yes".to_string() } else { String::new() }
+}
+
+fn trip_fields(trip: &Trip) -> Vec<Field> {
+    let summary = &trip.summary;
+    let dep = &summary.departure_anchor;
+    let arr = &summary.arrival_anchor;
+
+    vec![
+        ("from", summary.departure_display_name.clone()),
+        ("to", summary.arrival_display_name.clone()),
+        ("date", trip.departure_time().map(|t| t.format("%d.%m.%Y").to_string()).unwrap_or_default()),
+        ("departure", format_clock_time(trip.departure_time())),
+        ("departure_aimed", format_clock_time(parse_time(&dep.time_aimed))),
+        ("arrival", format_clock_time(trip.arrival_time())),
+        ("arrival_aimed", format_clock_time(parse_time(&arr.time_aimed))),
+        ("delay", signed(dep.delay_minutes())),
+        ("arrival_delay", signed(arr.delay_minutes())),
+        ("quay", dep.quay_name().unwrap_or_default().to_string()),
+        ("arrival_quay", arr.quay_name().unwrap_or_default().to_string()),
+        ("platform_changed", flag(trip.has_platform_change())),
+        ("line", dep.transport_designation.as_ref().map(|t| t.to_string()).unwrap_or_default()),
+        ("direction", dep.direction.clone().unwrap_or_default()),
+        ("duration", trip.duration_minutes().map(|d| d.to_string()).unwrap_or_default()),
+        ("transfers", trip.transfers().to_string()),
+        ("occupancy", summary.occupancy_second_class_max.clone().unwrap_or_default()),
+        ("occupancy_first_class", summary.occupancy_first_class_max.clone().unwrap_or_default()),
+        ("cancelled", flag(trip.is_cancelled())),
+    ]
+}
+
+fn leg_fields_of(pt: &PtRideLeg, cancelled: bool) -> Vec<Field> {
+    let dep = &pt.departure_stop_point;
+    let arr = &pt.arrival_stop_point;
+    let aimed = |t: &Option<StopTime>| format_clock_time(t.as_ref().and_then(|t| parse_time(&t.time_aimed)));
+    let changed = [&dep.quay, &arr.quay].iter().any(|q| q.as_ref().is_some_and(|q| q.changed));
+
+    vec![
+        ("from", dep.display_name.clone()),
+        ("to", arr.display_name.clone()),
+        ("departure", format_clock_time(dep.departure_time.as_ref().and_then(StopTime::time))),
+        ("departure_aimed", aimed(&dep.departure_time)),
+        ("arrival", format_clock_time(arr.arrival_time.as_ref().and_then(StopTime::time))),
+        ("arrival_aimed", aimed(&arr.arrival_time)),
+        ("delay", signed(dep.departure_time.as_ref().and_then(StopTime::delay_minutes))),
+        ("arrival_delay", signed(arr.arrival_time.as_ref().and_then(StopTime::delay_minutes))),
+        ("quay", dep.quay_name().unwrap_or_default().to_string()),
+        ("arrival_quay", arr.quay_name().unwrap_or_default().to_string()),
+        ("platform_changed", flag(changed)),
+        ("line", pt.first_transport_designation.as_ref().map(|t| t.to_string()).unwrap_or_default()),
+        ("direction", pt.direction.clone().unwrap_or_default()),
+        ("occupancy", dep.occupancy_second_class.clone().unwrap_or_default()),
+        ("occupancy_first_class", dep.occupancy_first_class.clone().unwrap_or_default()),
+        ("cancelled", flag(cancelled)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+
+    fn trip() -> Trip {
+        trips("sbb_api_response_0.json").remove(0)
+    }
+
+    fn render(template: &str, trip: &Trip) -> String {
+        template.parse::<Template>().unwrap().render(trip)
+    }
+
+    #[test]
+    fn test_fields_and_sections() {
+        let mut trip = trip();
+        let template = "{departure}{?delay} ({delay}){/delay}{!delay} on time{/delay} → {to}";
+        assert_eq!(render(template, &trip), "13:00 on time → Bern");
+
+        trip.summary.departure_anchor.time_expected = Some("2026-02-22T13:03:00+01:00".to_string());
+        assert_eq!(render(template, &trip), "13:03 (+3) → Bern");
+        assert_eq!(render("{{{transfers}}}", &trip), "{1}");
+        // The documented status-bar example
+        assert_eq!(render("{departure}{?delay} ({delay}){/delay} {quay}", &trip), "13:03 (+3) Gl. 19");
+    }
+
+    #[test]
+    fn test_legs() {
+        let trip = trip();
+        let rendered = render("{from}:{#legs} {from}-{to}{/legs}", &trip);
+        assert_eq!(rendered, "Basel SBB: Basel SBB-Olten Olten-Bern");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |t: &str| t.parse::<Template>().unwrap_err().to_string();
+        assert_eq!(err("{nope}"), "unknown template field `nope`");
+        assert_eq!(err("{?delay}late"), "missing `{/delay}`");
+        assert_eq!(err("{?delay}x{/quay}"), "expected `{/delay}`, found `{/quay}`");
+        assert_eq!(err("{/delay}"), "`{/delay}` without opening tag");
+        assert_eq!(err("{line"), "unclosed `{line`");
+        assert_eq!(err("a}b"), "unmatched `}`, write `}}` for a literal brace");
+        assert!("{#legs}{#legs}{/legs}{/legs}".parse::<Template>().is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use chrono::{DateTime, TimeZone, Utc};
+use futures::stream::{self, Stream};
+use serde::Serialize;
+use simple_error::SimpleError;
+use std::time::Duration;
+
+use crate::connections::get_connections;
+use crate::diff::{diff_trips, TripChange};
+use crate::models::location::SearchDateTimeType;
+use crate::models::trip::{parse_time, Trip};
+use crate::Language;
+
+/// Polling limits for [`watch_trip`].
+#[derive(Debug, Clone, Copy)]
+pub struct WatchOptions {
+    /// Used when the API does not say when to refresh (`TripMeta::next_refresh`).
+    pub default_interval: Duration,
+    /// Lower bound on the delay between two searches, whatever the API asks for.
+    pub min_interval: Duration,
+    /// Upper bound on the delay between two searches.
+    pub max_interval: Duration,
+    /// Language of the refreshed connection's texts; German when `None`.
+    pub language: Option<Language>,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        WatchOptions {
+            default_interval: Duration::from_secs(60),
+            min_interval: Duration::from_secs(30),
+            max_interval: Duration::from_secs(300),
+            language: None,
+        }
+    }
+}
+
+/// Something [`watch_trip`] noticed about the watched connection.
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WatchEvent {
+    /// The connection changed; `trip` is the refreshed version.
+    Changed { trip: Box<Trip>, changes: Vec<TripChange> },
+    /// The connection is no longer part of the search results.
+    Missing,
+    /// The connection has arrived. This is the last event of the stream.
+    Arrived { trip: Box<Trip> },
+}
+
+struct WatchState {
+    trip: Trip,
+    options: WatchOptions,
+    missing: bool,
+    done: bool,
+}
+
+/// Monitor a connection until it arrives.
+///
+/// The trip is searched again on the schedule suggested by the API (`TripMeta::next_refresh`,
+/// clamped to `options`), and an event is yielded whenever something changed. Failed searches
+/// are yielded as errors and retried on the next tick, so callers decide whether to give up.
+/// The stream is not `Unpin`; pin it (e.g. with `Box::pin`) before polling.
+pub fn watch_trip(trip: Trip, options: WatchOptions) -> impl Stream<Item = Result<WatchEvent, SimpleError>> {
+    let state = WatchState { trip, options, missing: false, done: false };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if state.done {
+                return None;
+            }
+
+            tokio::time::sleep(next_interval(&state.trip, &state.options, Utc::now())).await;
+
+            if has_arrived(&state.trip, Utc::now()) {
+                state.done = true;
+                let trip = Box::new(state.trip.clone());
+                return Some((Ok(WatchEvent::Arrived { trip }), state));
+            }
+
+            match refresh_trip(&state.trip, state.options.language).await {
+                Err(e) => return Some((Err(e), state)),
+                Ok(None) if state.missing => continue,
+                Ok(None) => {
+                    state.missing = true;
+                    return Some((Ok(WatchEvent::Missing), state));
+                }
+                Ok(Some(fresh)) => {
+                    state.missing = false;
+                    let changes = diff_trips(&state.trip, &fresh);
+                    state.trip = fresh;
+                    if !changes.is_empty() {
+                        let trip = Box::new(state.trip.clone());
+                        return Some((Ok(WatchEvent::Changed { trip, changes }), state));
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Search the connection again and return its current version, or `None` when the search
+/// no longer contains it.
+pub async fn refresh_trip(trip: &Trip, language: Option<Language>) -> Result<Option<Trip>, SimpleError> {
+    let departure = &trip.summary.departure_anchor;
+    let arrival = &trip.summary.arrival_anchor;
+    let aimed = parse_time(&departure.time_aimed)
+        .ok_or_else(|| SimpleError::new("trip has no scheduled departure time"))?;
+    // Searches take the local wall-clock time, like the CLI does
+    let on = Utc.from_utc_datetime(&aimed.naive_local());
+
+    let resp = get_connections(
+        &departure.place_name,
+        departure.place_reference.as_deref(),
+        &arrival.place_name,
+        arrival.place_reference.as_deref(),
+        &on,
+        SearchDateTimeType::Departure,
+        language,
+    )
+    .await?;
+
+    Ok(find_trip(trip, &resp.trips).cloned())
+}
+
+/// Find `trip` among `candidates`: by id, or else by scheduled departure and line.
+pub(crate) fn find_trip<'a>(trip: &Trip, candidates: &'a [Trip]) -> Option<&'a Trip> {
+    let key = |t: &Trip| {
+        let anchor = &t.summary.departure_anchor;
+        (anchor.time_aimed.clone(), anchor.transport_designation.as_ref().map(|d| d.to_string()))
+    };
+    candidates
+        .iter()
+        .find(|c| c.meta.id == trip.meta.id)
+        .or_else(|| candidates.iter().find(|c| key(c) == key(trip)))
+}
+
+fn has_arrived(trip: &Trip, now: DateTime<Utc>) -> bool {
+    trip.arrival_time().map(|a| a.with_timezone(&Utc) <= now).unwrap_or(false)
+}
+
+/// How long to wait before the next search: the API's suggestion, clamped to the options,
+/// but never past the arrival, when it is known.
+fn next_interval(trip: &Trip, options: &WatchOptions, now: DateTime<Utc>) -> Duration {
+    let suggested = trip
+        .meta
+        .next_refresh
+        .filter(|s| *s > 0)
+        .map(|s| Duration::from_secs(s as u64))
+        .unwrap_or(options.default_interval)
+        .clamp(options.min_interval, options.max_interval.max(options.min_interval));
+
+    let until_arrival = trip
+        .arrival_time()
+        .and_then(|a| (a.with_timezone(&Utc) - now).to_std().ok());
+    until_arrival.map_or(suggested, |u| suggested.min(u.max(Duration::from_secs(1))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::trip::fixtures::trips;
+
+    #[test]
+    fn test_find_trip() {
+        let trips = trips("sbb_api_response_0.json");
+        let found = find_trip(&trips[2], &trips).unwrap();
+        assert_eq!(found.meta.id, trips[2].meta.id);
+
+        // Ids may change between searches; fall back to departure and line
+        let mut renamed = trips[2].clone();
+        renamed.meta.id = "other".to_string();
+        assert_eq!(find_trip(&renamed, &trips).unwrap().meta.id, trips[2].meta.id);
+
+        renamed.summary.departure_anchor.time_aimed = Some("2026-02-22T03:00:00+01:00".to_string());
+        assert!(find_trip(&renamed, &trips).is_none());
+    }
+
+    #[test]
+    fn test_next_interval() {
+        let trip = trips("sbb_api_response_0.json").remove(0);
+        let options = WatchOptions::default();
+        // Arrives 14:24 local, i.e. 13:24 UTC
+        let early = Utc.with_ymd_and_hms(2026, 2, 22, 12, 0, 0).unwrap();
+        assert_eq!(next_interval(&trip, &options, early), Duration::from_secs(60));
+
+        let slow = WatchOptions { min_interval: Duration::from_secs(120), ..options };
+        assert_eq!(next_interval(&trip, &slow, early), Duration::from_secs(120));
+
+        // Wake up at arrival rather than after it
+        let almost = Utc.with_ymd_and_hms(2026, 2, 22, 13, 23, 50).unwrap();
+        assert_eq!(next_interval(&trip, &options, almost), Duration::from_secs(10));
+        assert!(!has_arrived(&trip, almost));
+        assert!(has_arrived(&trip, almost + chrono::Duration::seconds(10)));
+
+        // Without an arrival time, keep the suggested pace instead of polling every second
+        let mut unknown = trip.clone();
+        unknown.summary.arrival_anchor.time_aimed = None;
+        unknown.summary.arrival_anchor.time_expected = None;
+        assert_eq!(next_interval(&unknown, &options, almost), Duration::from_secs(60));
+    }
+}